@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use sqlite::{Connection, State};
 
 macro_rules! ok(($result:expr) => ($result.unwrap()));
@@ -21,3 +23,17 @@ pub fn populate(connection: &Connection, count: usize) {
         assert_eq!(ok!(statement.next()), State::Done);
     }
 }
+
+pub fn populate_text(connection: &Connection, count: usize) {
+    let query = "CREATE TABLE text (a INTEGER, b TEXT, c BLOB)";
+    ok!(connection.execute(query));
+    let query = "INSERT INTO text (a, b, c) VALUES (?, ?, ?)";
+    let mut statement = ok!(connection.prepare(query));
+    for i in 1..(count + 1) {
+        ok!(statement.reset());
+        ok!(statement.bind((1, i as i64)));
+        ok!(statement.bind((2, &*format!("{i:0>64}"))));
+        ok!(statement.bind((3, &[i as u8; 64][..])));
+        assert_eq!(ok!(statement.next()), State::Done);
+    }
+}
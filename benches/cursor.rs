@@ -7,7 +7,7 @@ mod common;
 use sqlite::Value;
 use test::Bencher;
 
-use common::{create, populate};
+use common::{create, populate, populate_text};
 
 macro_rules! ok(($result:expr) => ($result.unwrap()));
 
@@ -56,6 +56,92 @@ fn read_try_next(bencher: &mut Bencher) {
     })
 }
 
+#[bench]
+fn read_for_each_row(bencher: &mut Bencher) {
+    let connection = create();
+    populate(&connection, 100);
+    let query = "SELECT * FROM data WHERE a > ? AND b > ?";
+    let mut statement = ok!(connection.prepare(query));
+
+    bencher.iter(|| {
+        ok!(statement.reset());
+        ok!(statement.bind((1, 42)));
+        ok!(statement.bind((2, 42.0)));
+        let mut count = 0;
+        ok!(statement.for_each_row(|row| {
+            assert!(row.read::<i64, _>(0) > 42);
+            assert!(row.read::<f64, _>(1) > 42.0);
+            count += 1;
+            Ok(())
+        }));
+        assert_eq!(count, 100 - 42);
+    })
+}
+
+#[bench]
+fn read_next_text(bencher: &mut Bencher) {
+    let connection = create();
+    populate_text(&connection, 100);
+    let query = "SELECT * FROM text WHERE a > ?";
+    let mut statement = ok!(connection.prepare(query));
+
+    bencher.iter(|| {
+        let mut count = 0;
+        for row in statement
+            .iter()
+            .bind((1, 42))
+            .unwrap()
+            .map(|row| row.unwrap())
+        {
+            assert_eq!(row.read::<&str, _>(1).len(), 64);
+            assert_eq!(row.read::<&[u8], _>(2).len(), 64);
+            count += 1;
+        }
+        assert_eq!(count, 100 - 42);
+    })
+}
+
+#[bench]
+fn read_rows_ref(bencher: &mut Bencher) {
+    let connection = create();
+    populate(&connection, 100);
+    let query = "SELECT * FROM data WHERE a > ? AND b > ?";
+    let mut statement = ok!(connection.prepare(query));
+
+    bencher.iter(|| {
+        let mut rows = statement
+            .rows_ref()
+            .bind::<&[Value]>(&[42.into(), 42.0.into()][..])
+            .unwrap();
+        let mut count = 0;
+        while let Some(row) = ok!(rows.next()) {
+            assert!(ok!(i64::try_from(ok!(row.read_ref(0)))) > 42);
+            assert!(ok!(f64::try_from(ok!(row.read_ref(1)))) > 42.0);
+            count += 1;
+        }
+        assert_eq!(count, 100 - 42);
+    })
+}
+
+#[bench]
+fn read_rows_ref_text(bencher: &mut Bencher) {
+    let connection = create();
+    populate_text(&connection, 100);
+    let query = "SELECT * FROM text WHERE a > ?";
+    let mut statement = ok!(connection.prepare(query));
+
+    bencher.iter(|| {
+        let mut rows = statement.rows_ref().bind((1, 42)).unwrap();
+        let mut count = 0;
+        while let Some(row) = ok!(rows.next()) {
+            assert_eq!(ok!(<&str>::try_from(ok!(row.read_ref(1)))).len(), 64);
+            assert_eq!(ok!(<&[u8]>::try_from(ok!(row.read_ref(2)))).len(), 64);
+            count += 1;
+        }
+        assert_eq!(count, 100 - 42);
+    })
+}
+
 #[bench]
 fn write(bencher: &mut Bencher) {
     let connection = create();
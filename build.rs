@@ -0,0 +1,20 @@
+use std::env;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=SQLITE_ENABLE_SESSION");
+    println!("cargo:rerun-if-env-changed=SQLITE_ENABLE_PREUPDATE_HOOK");
+    // The bundled SQLite is compiled with the options given via environment variables, and the
+    // session extension is otherwise left out, which would surface only as link errors.
+    if env::var_os("CARGO_FEATURE_SESSION").is_some()
+        && env::var_os("CARGO_FEATURE_BUNDLED").is_some()
+    {
+        for name in ["SQLITE_ENABLE_SESSION", "SQLITE_ENABLE_PREUPDATE_HOOK"] {
+            if env::var(name).map_or(true, |value| value == "0") {
+                panic!(
+                    "the session feature together with the bundled feature requires {name}=1 to \
+                     be set in the environment"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! Derive macros for the [sqlite][1] package.
+//!
+//! The macros are not supposed to be used directly; instead, enable the `derive` feature of the
+//! `sqlite` package, which reexports them.
+//!
+//! ## Attributes
+//!
+//! Fields can be annotated with `#[sqlite(...)]` containing the following options:
+//!
+//! * `rename = "..."` uses the given name instead of the field name;
+//! * `default` uses `Default::default()` when the column is absent for `FromRow` and skips
+//!   binding when the parameter is absent for `Bindable`;
+//! * `flatten` delegates to the `FromRow` or `Bindable` implementation of the field type; and
+//! * `skip` ignores the field, using `Default::default()` for `FromRow`.
+//!
+//! [1]: https://crates.io/crates/sqlite
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Field, Fields, LitStr, Result};
+
+/// Derive `FromRow`.
+///
+/// Named fields are read from the columns with the same names, and unnamed fields are read from
+/// the columns at the same positions.
+#[proc_macro_derive(FromRow, attributes(sqlite))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_row(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derive `Bindable`.
+///
+/// Named fields are bound to the parameters with the same names prefixed with a colon, and
+/// unnamed fields are bound to the parameters at the same positions.
+#[proc_macro_derive(Bindable, attributes(sqlite))]
+pub fn derive_bindable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    bindable(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Options {
+    rename: Option<String>,
+    default: bool,
+    flatten: bool,
+    skip: bool,
+}
+
+impl Options {
+    fn parse(field: &Field) -> Result<Self> {
+        let mut options = Options::default();
+        for attribute in field.attrs.iter() {
+            if !attribute.path().is_ident("sqlite") {
+                continue;
+            }
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    options.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("default") {
+                    options.default = true;
+                } else if meta.path.is_ident("flatten") {
+                    options.flatten = true;
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else {
+                    return Err(meta.error("unsupported attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        if options.flatten && (options.rename.is_some() || options.default || options.skip) {
+            return Err(Error::new_spanned(
+                field,
+                "`flatten` cannot be combined with other attributes",
+            ));
+        }
+        if field.ident.is_none() && options.rename.is_some() {
+            return Err(Error::new_spanned(
+                field,
+                "`rename` is not supported for unnamed fields",
+            ));
+        }
+        Ok(options)
+    }
+}
+
+fn fields(input: &DeriveInput) -> Result<&Fields> {
+    match input.data {
+        Data::Struct(ref data) => Ok(&data.fields),
+        _ => Err(Error::new_spanned(input, "only structs are supported")),
+    }
+}
+
+fn from_row(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = fields(&input)?;
+    let mut values = Vec::with_capacity(fields.len());
+    for (index, field) in fields.iter().enumerate() {
+        let options = Options::parse(field)?;
+        let value = if options.skip {
+            quote!(::core::default::Default::default())
+        } else if options.flatten {
+            quote!(::sqlite::FromRow::from_row(statement)?)
+        } else {
+            let column = match field.ident {
+                Some(ref ident) => {
+                    let name = options.rename.unwrap_or_else(|| unraw(ident));
+                    quote!(#name)
+                }
+                _ => quote!(#index),
+            };
+            if options.default {
+                quote! {
+                    match ::sqlite::ColumnIndex::index(#column, statement) {
+                        Ok(_) => statement.read(#column)?,
+                        _ => ::core::default::Default::default(),
+                    }
+                }
+            } else {
+                quote!(statement.read(#column)?)
+            }
+        };
+        values.push(match field.ident {
+            Some(ref ident) => quote!(#ident: #value),
+            _ => value,
+        });
+    }
+    let body = match fields {
+        Fields::Named(_) => quote!(Self { #(#values,)* }),
+        Fields::Unnamed(_) => quote!(Self(#(#values,)*)),
+        Fields::Unit => quote!(Self),
+    };
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::sqlite::FromRow for #name #type_generics #where_clause {
+            fn from_row(statement: &::sqlite::Statement) -> ::sqlite::Result<Self> {
+                Ok(#body)
+            }
+        }
+    })
+}
+
+fn bindable(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = fields(&input)?;
+    let mut statements = Vec::with_capacity(fields.len());
+    for (index, field) in fields.iter().enumerate() {
+        let options = Options::parse(field)?;
+        if options.skip {
+            continue;
+        }
+        let member = match field.ident {
+            Some(ref ident) => quote!(#ident),
+            _ => {
+                let index = syn::Index::from(index);
+                quote!(#index)
+            }
+        };
+        if options.flatten {
+            statements.push(quote!(::sqlite::Bindable::bind(self.#member, statement)?;));
+            continue;
+        }
+        let parameter = match field.ident {
+            Some(ref ident) => {
+                let name = format!(":{}", options.rename.unwrap_or_else(|| unraw(ident)));
+                quote!(#name)
+            }
+            _ => {
+                let index = index + 1;
+                quote!(#index)
+            }
+        };
+        let statement = quote! {
+            ::sqlite::BindableWithIndex::bind(self.#member, statement, #parameter)?;
+        };
+        statements.push(if options.default {
+            quote! {
+                if ::sqlite::ParameterIndex::index(#parameter, statement).is_ok() {
+                    #statement
+                }
+            }
+        } else {
+            statement
+        });
+    }
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::sqlite::Bindable for #name #type_generics #where_clause {
+            #[allow(unused_variables)]
+            fn bind(self, statement: &mut ::sqlite::Statement) -> ::sqlite::Result<()> {
+                #(#statements)*
+                Ok(())
+            }
+        }
+    })
+}
+
+fn unraw(ident: &syn::Ident) -> String {
+    let name = ident.to_string();
+    match name.strip_prefix("r#") {
+        Some(name) => name.to_string(),
+        _ => name,
+    }
+}
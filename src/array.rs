@@ -0,0 +1,260 @@
+use core::ffi::{c_char, c_int, c_void};
+use std::ffi::CStr;
+use std::rc::Rc;
+
+use crate::connection::Connection;
+use crate::error::Result;
+use crate::statement::{BindableWithIndex, ParameterIndex, Statement};
+use crate::value::Value;
+
+const NAME: &CStr = c"rarray";
+
+const POINTER_TYPE: &CStr = c"sqlite::rarray";
+
+#[derive(Clone)]
+enum Array {
+    Float(Rc<Vec<f64>>),
+    Integer(Rc<Vec<i64>>),
+    String(Rc<Vec<String>>),
+    Value(Rc<Vec<Value>>),
+}
+
+#[repr(C)]
+struct Cursor {
+    base: ffi::sqlite3_vtab_cursor,
+    array: Option<Array>,
+    position: usize,
+}
+
+static MODULE: ffi::sqlite3_module = ffi::sqlite3_module {
+    iVersion: 1,
+    xCreate: None,
+    xConnect: Some(connect_callback),
+    xBestIndex: Some(best_index_callback),
+    xDisconnect: Some(disconnect_callback),
+    xDestroy: Some(disconnect_callback),
+    xOpen: Some(open_callback),
+    xClose: Some(close_callback),
+    xFilter: Some(filter_callback),
+    xNext: Some(next_callback),
+    xEof: Some(eof_callback),
+    xColumn: Some(column_callback),
+    xRowid: Some(rowid_callback),
+    xUpdate: None,
+    xBegin: None,
+    xSync: None,
+    xCommit: None,
+    xRollback: None,
+    xFindFunction: None,
+    xRename: None,
+    xSavepoint: None,
+    xRelease: None,
+    xRollbackTo: None,
+    xShadowName: None,
+    xIntegrity: None,
+};
+
+macro_rules! implement(
+    ($type:ty, $variant:ident) => {
+        impl BindableWithIndex for Rc<Vec<$type>> {
+            #[inline]
+            fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+                bind(statement, index, Array::$variant(self))
+            }
+        }
+    }
+);
+
+implement!(f64, Float);
+implement!(i64, Integer);
+implement!(String, String);
+implement!(Value, Value);
+
+pub fn create(connection: &Connection) -> Result<()> {
+    unsafe {
+        ok!(
+            connection.as_raw(),
+            ffi::sqlite3_create_module_v2(
+                connection.as_raw(),
+                NAME.as_ptr(),
+                &MODULE,
+                std::ptr::null_mut(),
+                None,
+            )
+        );
+    }
+    Ok(())
+}
+
+fn bind<T: ParameterIndex>(statement: &mut Statement, index: T, array: Array) -> Result<()> {
+    let index = index.index(statement)?;
+    let raw = statement.as_raw();
+    unsafe {
+        ok!(
+            ffi::sqlite3_db_handle(raw),
+            ffi::sqlite3_bind_pointer(
+                raw,
+                index as c_int,
+                Box::into_raw(Box::new(array)) as *mut _,
+                POINTER_TYPE.as_ptr(),
+                Some(destroy_callback),
+            )
+        );
+    }
+    Ok(())
+}
+
+extern "C" fn connect_callback(
+    db: *mut ffi::sqlite3,
+    _: *mut c_void,
+    _: c_int,
+    _: *const *const c_char,
+    vtab: *mut *mut ffi::sqlite3_vtab,
+    _: *mut *mut c_char,
+) -> c_int {
+    unsafe {
+        let schema = c"CREATE TABLE x (value, pointer HIDDEN)";
+        let code = ffi::sqlite3_declare_vtab(db, schema.as_ptr());
+        if code == ffi::SQLITE_OK {
+            *vtab = Box::into_raw(Box::new(std::mem::zeroed::<ffi::sqlite3_vtab>()));
+        }
+        code
+    }
+}
+
+extern "C" fn best_index_callback(
+    _: *mut ffi::sqlite3_vtab,
+    info: *mut ffi::sqlite3_index_info,
+) -> c_int {
+    unsafe {
+        let info = &mut *info;
+        for index in 0..info.nConstraint.max(0) as usize {
+            let constraint = &*info.aConstraint.add(index);
+            if constraint.iColumn != 1 || constraint.op != ffi::SQLITE_INDEX_CONSTRAINT_EQ as u8 {
+                continue;
+            }
+            // Prefer plans in which the array is available.
+            if constraint.usable == 0 {
+                return ffi::SQLITE_CONSTRAINT;
+            }
+            let usage = &mut *info.aConstraintUsage.add(index);
+            usage.argvIndex = 1;
+            usage.omit = 1;
+            info.idxNum = 1;
+            info.estimatedCost = 1.0;
+            info.estimatedRows = 100;
+            return ffi::SQLITE_OK;
+        }
+        info.idxNum = 0;
+        info.estimatedCost = f64::MAX;
+        info.estimatedRows = 0;
+    }
+    ffi::SQLITE_OK
+}
+
+extern "C" fn disconnect_callback(vtab: *mut ffi::sqlite3_vtab) -> c_int {
+    unsafe {
+        let vtab = Box::from_raw(vtab);
+        ffi::sqlite3_free(vtab.zErrMsg as *mut _);
+    }
+    ffi::SQLITE_OK
+}
+
+extern "C" fn open_callback(
+    _: *mut ffi::sqlite3_vtab,
+    cursor: *mut *mut ffi::sqlite3_vtab_cursor,
+) -> c_int {
+    unsafe {
+        let value = Box::new(Cursor {
+            base: std::mem::zeroed(),
+            array: None,
+            position: 0,
+        });
+        *cursor = Box::into_raw(value) as *mut ffi::sqlite3_vtab_cursor;
+    }
+    ffi::SQLITE_OK
+}
+
+extern "C" fn close_callback(cursor: *mut ffi::sqlite3_vtab_cursor) -> c_int {
+    unsafe { drop(Box::from_raw(cursor as *mut Cursor)) };
+    ffi::SQLITE_OK
+}
+
+extern "C" fn filter_callback(
+    cursor: *mut ffi::sqlite3_vtab_cursor,
+    index_number: c_int,
+    _: *const c_char,
+    count: c_int,
+    arguments: *mut *mut ffi::sqlite3_value,
+) -> c_int {
+    unsafe {
+        let cursor = &mut *(cursor as *mut Cursor);
+        cursor.array = None;
+        cursor.position = 0;
+        if index_number == 1 && count > 0 {
+            let array = ffi::sqlite3_value_pointer(*arguments, POINTER_TYPE.as_ptr());
+            if !array.is_null() {
+                cursor.array = Some((*(array as *const Array)).clone());
+            }
+        }
+    }
+    ffi::SQLITE_OK
+}
+
+extern "C" fn next_callback(cursor: *mut ffi::sqlite3_vtab_cursor) -> c_int {
+    unsafe { (*(cursor as *mut Cursor)).position += 1 };
+    ffi::SQLITE_OK
+}
+
+extern "C" fn eof_callback(cursor: *mut ffi::sqlite3_vtab_cursor) -> c_int {
+    let cursor = unsafe { &*(cursor as *mut Cursor) };
+    let length = match &cursor.array {
+        Some(Array::Float(values)) => values.len(),
+        Some(Array::Integer(values)) => values.len(),
+        Some(Array::String(values)) => values.len(),
+        Some(Array::Value(values)) => values.len(),
+        None => 0,
+    };
+    c_int::from(cursor.position >= length)
+}
+
+extern "C" fn column_callback(
+    cursor: *mut ffi::sqlite3_vtab_cursor,
+    context: *mut ffi::sqlite3_context,
+    index: c_int,
+) -> c_int {
+    unsafe {
+        let cursor = &*(cursor as *mut Cursor);
+        let position = cursor.position;
+        match (index, &cursor.array) {
+            (0, Some(Array::Float(values))) => {
+                ffi::sqlite3_result_double(context, values[position])
+            }
+            (0, Some(Array::Integer(values))) => {
+                ffi::sqlite3_result_int64(context, values[position])
+            }
+            (0, Some(Array::String(values))) => ffi::sqlite3_result_text64(
+                context,
+                values[position].as_ptr() as *const _,
+                values[position].len() as u64,
+                transient!(),
+                ffi::SQLITE_UTF8 as _,
+            ),
+            (0, Some(Array::Value(values))) => crate::vtab::result(context, &values[position]),
+            _ => ffi::sqlite3_result_null(context),
+        }
+    }
+    ffi::SQLITE_OK
+}
+
+extern "C" fn rowid_callback(
+    cursor: *mut ffi::sqlite3_vtab_cursor,
+    rowid: *mut ffi::sqlite3_int64,
+) -> c_int {
+    unsafe { *rowid = (*(cursor as *mut Cursor)).position as ffi::sqlite3_int64 + 1 };
+    ffi::SQLITE_OK
+}
+
+extern "C" fn destroy_callback(array: *mut c_void) {
+    unsafe { drop(Box::from_raw(array as *mut Array)) };
+}
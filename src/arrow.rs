@@ -0,0 +1,395 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use ::arrow_array::builder::{
+    BinaryBuilder, BooleanBuilder, Float32Builder, Float64Builder, Int16Builder, Int32Builder,
+    Int64Builder, Int8Builder, LargeBinaryBuilder, LargeStringBuilder, StringBuilder,
+    UInt16Builder, UInt32Builder, UInt64Builder, UInt8Builder,
+};
+use ::arrow_array::cast::AsArray;
+use ::arrow_array::types::{
+    Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type,
+    UInt64Type, UInt8Type,
+};
+use ::arrow_array::{Array, ArrayRef, NullArray, RecordBatch};
+use ::arrow_schema::{DataType, Field, Schema, SchemaRef};
+
+use crate::connection::Connection;
+use crate::error::{Error, Result};
+use crate::statement::{State, Statement};
+use crate::value::Value;
+
+impl Statement<'_> {
+    /// Execute the statement and collect the resulting rows into record batches.
+    ///
+    /// Each batch contains at most `batch_size` rows. The data type of each column is taken from
+    /// the schema hint if given, which should have as many fields as there are columns;
+    /// otherwise, it is derived from the declared type of the column according to the rules of
+    /// type affinity as long as the values in the first batch fit it, and it is inferred from
+    /// those values if the column has no declared type with a known affinity or has values that
+    /// do not fit it. When inferring, integers mixed with reals are treated as reals, other mixed
+    /// values are treated as binary if any of them is a blob and as text otherwise, and columns
+    /// with no values are treated as text. Values in later batches that do not fit the data type
+    /// of their column result in an error. Supported data types are `Null`, `Boolean`, signed and
+    /// unsigned integers, `Float32`, `Float64`, `Utf8`, `LargeUtf8`, `Binary`, and `LargeBinary`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use arrow_array::cast::AsArray;
+    /// use arrow_array::types::Int64Type;
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name TEXT)");
+    /// # connection.execute("INSERT INTO users VALUES (1, 'Alice'), (2, 'Bob'), (3, NULL)");
+    /// let mut statement = connection.prepare("SELECT * FROM users")?;
+    /// let batches = statement.to_record_batches(2, None)?;
+    /// assert_eq!(batches.len(), 2);
+    /// assert_eq!(batches[0].num_rows(), 2);
+    /// assert_eq!(batches[1].column(0).as_primitive::<Int64Type>().value(0), 3);
+    /// assert!(batches[1].column(1).is_null(0));
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    pub fn to_record_batches(
+        &mut self,
+        batch_size: usize,
+        schema_hint: Option<&Schema>,
+    ) -> Result<Vec<RecordBatch>> {
+        let batch_size = batch_size.max(1);
+        let count = self.column_count();
+        let mut schema: Option<SchemaRef> = match schema_hint {
+            Some(schema) if schema.fields().len() != count => {
+                raise!(
+                    "expected {count} fields in the schema but found {}",
+                    schema.fields().len(),
+                );
+            }
+            Some(schema) => Some(Arc::new(schema.clone())),
+            _ => None,
+        };
+        let mut types = (0..count)
+            .map(|index| Ok(self.column_decltype(index)?.and_then(affinity)))
+            .collect::<Result<Vec<_>>>()?;
+        let mut batches = Vec::new();
+        loop {
+            let mut columns = vec![Vec::with_capacity(batch_size); count];
+            let mut done = false;
+            while columns.first().map_or(0, Vec::len) < batch_size {
+                if let State::Done = self.next()? {
+                    done = true;
+                    break;
+                }
+                for (index, column) in columns.iter_mut().enumerate() {
+                    column.push(self.read::<Value, _>(index)?);
+                }
+            }
+            if columns.first().is_none_or(Vec::is_empty) {
+                break;
+            }
+            let schema = schema.get_or_insert_with(|| {
+                let fields = self
+                    .column_names()
+                    .iter()
+                    .zip(types.iter_mut().zip(&columns))
+                    .map(|(name, (kind, values))| {
+                        let kind = match kind.take() {
+                            Some(kind) if fits(&kind, values) => kind,
+                            _ => infer(values),
+                        };
+                        Field::new(name, kind, true)
+                    })
+                    .collect::<Vec<_>>();
+                Arc::new(Schema::new(fields))
+            });
+            let offset = batches.len() * batch_size;
+            let arrays = schema
+                .fields()
+                .iter()
+                .zip(&columns)
+                .map(|(field, values)| build(field, values, offset))
+                .collect::<Result<Vec<_>>>()?;
+            match RecordBatch::try_new(schema.clone(), arrays) {
+                Ok(batch) => batches.push(batch),
+                Err(error) => raise!("failed to create a record batch ({error})"),
+            }
+            if done {
+                break;
+            }
+        }
+        Ok(batches)
+    }
+}
+
+impl Connection {
+    /// Insert the rows of a record batch into a table.
+    ///
+    /// The fields of the batch are mapped to the columns of the table by name, and the rows are
+    /// inserted in a single transaction unless the connection is already inside one. The
+    /// function returns the number of inserted rows. The table name may be qualified with a
+    /// schema; see `Connection::bulk_insert`. See `Statement::to_record_batches` for the
+    /// supported data types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use arrow_array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name TEXT)");
+    /// let batch = RecordBatch::try_from_iter([
+    ///     ("id", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+    ///     ("name", Arc::new(StringArray::from(vec![Some("Alice"), None])) as ArrayRef),
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(connection.insert_record_batch("users", &batch)?, 2);
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    pub fn insert_record_batch(&self, table: &str, batch: &RecordBatch) -> Result<usize> {
+        let schema = batch.schema();
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect::<Vec<_>>();
+        for field in schema.fields() {
+            if !is_supported(field.data_type()) {
+                raise!("the data type is not supported ({})", field.data_type());
+            }
+        }
+        let mut inserter = self
+            .bulk_insert(table, &columns)?
+            .with_batch_size(usize::MAX);
+        let mut values = Vec::with_capacity(columns.len());
+        for row in 0..batch.num_rows() {
+            values.clear();
+            for column in batch.columns() {
+                values.push(read(column, row)?);
+            }
+            inserter.insert(&values[..])?;
+        }
+        inserter.finish()
+    }
+}
+
+macro_rules! build(
+    ($builder:ident, $values:expr, |$value:ident| $convert:expr) => ({
+        let mut builder = $builder::with_capacity($values.len());
+        for (index, value) in $values.iter().enumerate() {
+            match value {
+                Value::Null => builder.append_null(),
+                $value => builder.append_value($convert.map_err(|error| (index, error))?),
+            }
+        }
+        Arc::new(builder.finish()) as ArrayRef
+    });
+    ($builder:ident, $values:expr, |$value:ident| $convert:expr, $capacity:expr) => ({
+        let mut builder = $builder::with_capacity($values.len(), $capacity);
+        for (index, value) in $values.iter().enumerate() {
+            match value {
+                Value::Null => builder.append_null(),
+                $value => builder.append_value($convert.map_err(|error| (index, error))?),
+            }
+        }
+        Arc::new(builder.finish()) as ArrayRef
+    });
+);
+
+fn build(field: &Field, values: &[Value], offset: usize) -> Result<ArrayRef> {
+    if !is_supported(field.data_type()) {
+        raise!("the data type is not supported ({})", field.data_type());
+    }
+    match convert(field.data_type(), values) {
+        Ok(array) => Ok(array),
+        Err((index, error)) => Err(Error {
+            message: Some(format!(
+                "failed to convert {:?} in column {} at row {}",
+                values[index],
+                field.name(),
+                offset + index,
+            )),
+            ..error
+        }),
+    }
+}
+
+fn convert(kind: &DataType, values: &[Value]) -> std::result::Result<ArrayRef, (usize, Error)> {
+    let capacity = values
+        .iter()
+        .map(|value| match value {
+            Value::Binary(value) => value.len(),
+            Value::String(value) => value.len(),
+            _ => 0,
+        })
+        .sum::<usize>();
+    let array = match kind {
+        DataType::Null => {
+            for (index, value) in values.iter().enumerate() {
+                null(value).map_err(|error| (index, error))?;
+            }
+            Arc::new(NullArray::new(values.len())) as ArrayRef
+        }
+        DataType::Boolean => build!(BooleanBuilder, values, |value| bool::try_from(value)),
+        DataType::Int8 => build!(Int8Builder, values, |value| i8::try_from(value)),
+        DataType::Int16 => build!(Int16Builder, values, |value| i16::try_from(value)),
+        DataType::Int32 => build!(Int32Builder, values, |value| i32::try_from(value)),
+        DataType::Int64 => build!(Int64Builder, values, |value| i64::try_from(value)),
+        DataType::UInt8 => build!(UInt8Builder, values, |value| u8::try_from(value)),
+        DataType::UInt16 => build!(UInt16Builder, values, |value| u16::try_from(value)),
+        DataType::UInt32 => build!(UInt32Builder, values, |value| u32::try_from(value)),
+        DataType::UInt64 => build!(UInt64Builder, values, |value| u64::try_from(value)),
+        DataType::Float32 => build!(Float32Builder, values, |value| float(value)
+            .and_then(|value| f32::try_from(&Value::Float(value)))),
+        DataType::Float64 => build!(Float64Builder, values, |value| float(value)),
+        DataType::Utf8 => build!(StringBuilder, values, |value| text(value), capacity),
+        DataType::LargeUtf8 => build!(LargeStringBuilder, values, |value| text(value), capacity),
+        DataType::Binary => build!(BinaryBuilder, values, |value| binary(value), capacity),
+        DataType::LargeBinary => {
+            build!(LargeBinaryBuilder, values, |value| binary(value), capacity)
+        }
+        _ => unreachable!(),
+    };
+    Ok(array)
+}
+
+fn read(array: &ArrayRef, index: usize) -> Result<Value> {
+    if array.is_null(index) {
+        return Ok(Value::Null);
+    }
+    Ok(match array.data_type() {
+        DataType::Null => Value::Null,
+        DataType::Boolean => array.as_boolean().value(index).into(),
+        DataType::Int8 => array.as_primitive::<Int8Type>().value(index).into(),
+        DataType::Int16 => array.as_primitive::<Int16Type>().value(index).into(),
+        DataType::Int32 => array.as_primitive::<Int32Type>().value(index).into(),
+        DataType::Int64 => array.as_primitive::<Int64Type>().value(index).into(),
+        DataType::UInt8 => array.as_primitive::<UInt8Type>().value(index).into(),
+        DataType::UInt16 => array.as_primitive::<UInt16Type>().value(index).into(),
+        DataType::UInt32 => array.as_primitive::<UInt32Type>().value(index).into(),
+        DataType::UInt64 => array.as_primitive::<UInt64Type>().value(index).try_into()?,
+        DataType::Float32 => array.as_primitive::<Float32Type>().value(index).into(),
+        DataType::Float64 => array.as_primitive::<Float64Type>().value(index).into(),
+        DataType::Utf8 => array.as_string::<i32>().value(index).into(),
+        DataType::LargeUtf8 => array.as_string::<i64>().value(index).into(),
+        DataType::Binary => array.as_binary::<i32>().value(index).into(),
+        DataType::LargeBinary => array.as_binary::<i64>().value(index).into(),
+        kind => raise!("the data type is not supported ({kind})"),
+    })
+}
+
+// Map a declared type to a data type following https://sqlite.org/datatype3.html.
+fn affinity(declared: &str) -> Option<DataType> {
+    let declared = declared.to_uppercase();
+    if declared.contains("INT") {
+        Some(DataType::Int64)
+    } else if ["CHAR", "CLOB", "TEXT"]
+        .iter()
+        .any(|name| declared.contains(name))
+    {
+        Some(DataType::Utf8)
+    } else if declared.contains("BLOB") {
+        Some(DataType::Binary)
+    } else if ["REAL", "FLOA", "DOUB"]
+        .iter()
+        .any(|name| declared.contains(name))
+    {
+        Some(DataType::Float64)
+    } else if declared.starts_with("BOOL") {
+        Some(DataType::Boolean)
+    } else {
+        None
+    }
+}
+
+fn binary(value: &Value) -> Result<Cow<'_, [u8]>> {
+    match value {
+        Value::Binary(value) => Ok(Cow::Borrowed(value)),
+        Value::Float(value) => Ok(Cow::Owned(value.to_string().into_bytes())),
+        Value::Integer(value) => Ok(Cow::Owned(value.to_string().into_bytes())),
+        Value::String(value) => Ok(Cow::Borrowed(value.as_bytes())),
+        _ => raise!("failed to convert"),
+    }
+}
+
+fn float(value: &Value) -> Result<f64> {
+    match *value {
+        Value::Float(value) => Ok(value),
+        Value::Integer(value) => Ok(value as f64),
+        _ => raise!("failed to convert"),
+    }
+}
+
+fn fits(kind: &DataType, values: &[Value]) -> bool {
+    values.iter().all(|value| {
+        matches!(
+            (kind, value),
+            (_, Value::Null)
+                | (DataType::Binary, _)
+                | (DataType::Boolean | DataType::Int64, Value::Integer(_))
+                | (DataType::Float64, Value::Float(_) | Value::Integer(_))
+                | (
+                    DataType::Utf8,
+                    Value::Float(_) | Value::Integer(_) | Value::String(_)
+                )
+        )
+    })
+}
+
+fn infer(values: &[Value]) -> DataType {
+    let mut kind = None;
+    for value in values {
+        let found = match value {
+            Value::Binary(_) => DataType::Binary,
+            Value::Float(_) => DataType::Float64,
+            Value::Integer(_) => DataType::Int64,
+            Value::String(_) => DataType::Utf8,
+            Value::Null => continue,
+        };
+        kind = Some(match (kind, found) {
+            (None, found) => found,
+            (Some(DataType::Int64), DataType::Float64)
+            | (Some(DataType::Float64), DataType::Int64) => DataType::Float64,
+            (Some(kind), found) if kind == found => kind,
+            (Some(DataType::Binary), _) | (_, DataType::Binary) => DataType::Binary,
+            _ => DataType::Utf8,
+        });
+    }
+    kind.unwrap_or(DataType::Utf8)
+}
+
+fn null(value: &Value) -> Result<()> {
+    match value {
+        Value::Null => Ok(()),
+        _ => raise!("failed to convert"),
+    }
+}
+
+fn text(value: &Value) -> Result<Cow<'_, str>> {
+    match value {
+        Value::Float(value) => Ok(Cow::Owned(value.to_string())),
+        Value::Integer(value) => Ok(Cow::Owned(value.to_string())),
+        Value::String(value) => Ok(Cow::Borrowed(value)),
+        _ => raise!("failed to convert"),
+    }
+}
+
+fn is_supported(kind: &DataType) -> bool {
+    matches!(
+        kind,
+        DataType::Null
+            | DataType::Boolean
+            | DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Utf8
+            | DataType::LargeUtf8
+            | DataType::Binary
+            | DataType::LargeBinary
+    )
+}
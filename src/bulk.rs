@@ -0,0 +1,187 @@
+use crate::connection::Connection;
+use crate::error::{Error, Result};
+use crate::statement::{Bindable, State, Statement};
+
+/// The default number of rows inserted per transaction.
+pub const DEFAULT_BATCH_SIZE: usize = 1000;
+
+/// An inserter of many rows into a table.
+///
+/// Rows are inserted via a single prepared statement and committed in batches. If the connection
+/// is already inside a transaction, the inserter leaves it to the caller to commit. Rows that have
+/// not been committed yet are rolled back if the inserter is dropped without calling `finish`.
+pub struct BulkInserter<'l> {
+    connection: &'l Connection,
+    statement: Statement<'l>,
+    batch_size: usize,
+    pending: usize,
+    transaction: bool,
+    count: usize,
+    inserted: usize,
+    failure: Option<(usize, Error)>,
+}
+
+impl<'l> BulkInserter<'l> {
+    /// Set the number of rows inserted per transaction.
+    ///
+    /// The default value is `DEFAULT_BATCH_SIZE`, and zero is treated as one.
+    #[inline]
+    pub fn with_batch_size(mut self, value: usize) -> Self {
+        self.batch_size = value.max(1);
+        self
+    }
+
+    /// Insert a row.
+    ///
+    /// The values are bound to the columns in the order the columns were given; see
+    /// `Statement::bind` for further details. In case of an error, the row is skipped, and the
+    /// returned error refers to the row by its index, which starts from zero and counts all rows
+    /// passed to the function.
+    pub fn insert<T: Bindable>(&mut self, row: T) -> Result<()> {
+        let index = self.count;
+        self.count += 1;
+        match self.try_insert(row) {
+            Ok(()) => {
+                self.inserted += 1;
+                self.pending += 1;
+                if self.pending >= self.batch_size {
+                    self.commit()?;
+                }
+                Ok(())
+            }
+            Err(error) => {
+                // A reset after a failed step reports the failure again.
+                let _ = self.statement.reset();
+                let error = Error {
+                    message: Some(format!(
+                        "failed to insert row {index} ({})",
+                        error.message.as_deref().unwrap_or("an unknown error"),
+                    )),
+                    ..error
+                };
+                if self.failure.is_none() {
+                    self.failure = Some((index, error.clone()));
+                }
+                Err(error)
+            }
+        }
+    }
+
+    /// Return the number of rows inserted so far, including those not committed yet.
+    ///
+    /// Rows lost due to the transaction being rolled back by SQLite are not counted.
+    #[inline]
+    pub fn inserted(&self) -> usize {
+        self.inserted
+    }
+
+    /// Return the index of the first row that failed to be inserted together with the error.
+    #[inline]
+    pub fn failure(&self) -> Option<(usize, &Error)> {
+        self.failure.as_ref().map(|(index, error)| (*index, error))
+    }
+
+    /// Commit the remaining rows and return the number of inserted rows.
+    pub fn finish(mut self) -> Result<usize> {
+        self.commit()?;
+        Ok(self.inserted)
+    }
+
+    fn try_insert<T: Bindable>(&mut self, row: T) -> Result<()> {
+        self.check_rollback()?;
+        if !self.transaction && self.is_autocommit() {
+            self.connection.execute("BEGIN")?;
+            self.transaction = true;
+        }
+        self.statement.reset()?;
+        unsafe {
+            ffi::sqlite3_clear_bindings(self.statement.as_raw());
+        }
+        self.statement.bind(row)?;
+        match self.statement.next()? {
+            State::Done => Ok(()),
+            State::Row => raise!("the statement returned a row"),
+        }
+    }
+
+    fn commit(&mut self) -> Result<()> {
+        self.statement.reset()?;
+        self.check_rollback()?;
+        if self.transaction {
+            // The flag is kept on failure for the transaction to be rolled back when dropped.
+            self.connection.execute("COMMIT")?;
+            self.transaction = false;
+        }
+        self.pending = 0;
+        Ok(())
+    }
+
+    // SQLite might roll back the transaction on its own, for instance, in case of SQLITE_FULL, in
+    // which case the rows inserted since the last commit are lost.
+    fn check_rollback(&mut self) -> Result<()> {
+        if self.transaction && self.is_autocommit() {
+            self.transaction = false;
+            self.inserted -= self.pending;
+            self.pending = 0;
+            raise!("the transaction has been rolled back");
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn is_autocommit(&self) -> bool {
+        unsafe { ffi::sqlite3_get_autocommit(self.connection.as_raw()) != 0 }
+    }
+}
+
+impl Drop for BulkInserter<'_> {
+    fn drop(&mut self) {
+        if self.transaction && !self.is_autocommit() {
+            let _ = self.statement.reset();
+            let _ = self.connection.execute("ROLLBACK");
+        }
+    }
+}
+
+pub fn new<'l, T: AsRef<str>>(
+    connection: &'l Connection,
+    table: &str,
+    columns: &[T],
+) -> Result<BulkInserter<'l>> {
+    if columns.is_empty() {
+        raise!("expected at least one column");
+    }
+    let names = columns
+        .iter()
+        .map(|column| quote(column.as_ref()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let parameters = vec!["?"; columns.len()].join(", ");
+    let statement = format!(
+        "INSERT INTO {} ({names}) VALUES ({parameters})",
+        quote_table(table),
+    );
+    Ok(BulkInserter {
+        connection,
+        statement: connection.prepare(statement)?,
+        batch_size: DEFAULT_BATCH_SIZE,
+        pending: 0,
+        transaction: false,
+        count: 0,
+        inserted: 0,
+        failure: None,
+    })
+}
+
+pub fn quote(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// Quote a table name that is optionally qualified with a schema, which is separated by the first
+// dot.
+pub fn quote_table(name: &str) -> String {
+    match name.split_once('.') {
+        Some((schema, name)) => format!("{}.{}", quote(schema), quote(name)),
+        _ => quote(name),
+    }
+}
@@ -0,0 +1,61 @@
+use ::chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc,
+};
+
+use crate::datetime::{format_date, format_offset, format_time, implement, Timestamp};
+use crate::error::{Error, Result};
+
+implement!(
+    DateTime<Utc>,
+    |value| format_date_time(&value.naive_utc()),
+    |timestamp| utc(timestamp)
+);
+implement!(
+    DateTime<FixedOffset>,
+    |value| {
+        format_date_time(&value.naive_local())
+            .map(|string| string + &format_offset(value.offset().local_minus_utc()))
+    },
+    |timestamp| {
+        FixedOffset::east_opt(timestamp.offset)
+            .and_then(|offset| Some(utc(timestamp)?.with_timezone(&offset)))
+    }
+);
+implement!(
+    NaiveDateTime,
+    |value| format_date_time(&value),
+    |timestamp| utc(timestamp).map(|value| value.naive_utc())
+);
+implement!(
+    NaiveDate,
+    |value| format_date(value.year(), value.month(), value.day()),
+    |timestamp| utc(timestamp).map(|value| value.date_naive())
+);
+implement!(
+    NaiveTime,
+    |value| Ok::<_, Error>(format_time(
+        value.hour(),
+        value.minute(),
+        value.second(),
+        value.nanosecond()
+    )),
+    |timestamp| utc(timestamp).map(|value| value.time())
+);
+
+fn format_date_time(value: &NaiveDateTime) -> Result<String> {
+    Ok(format!(
+        "{} {}",
+        format_date(value.year(), value.month(), value.day())?,
+        format_time(
+            value.hour(),
+            value.minute(),
+            value.second(),
+            value.nanosecond()
+        ),
+    ))
+}
+
+#[inline]
+fn utc(timestamp: Timestamp) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(timestamp.seconds, timestamp.nanoseconds)
+}
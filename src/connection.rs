@@ -3,19 +3,61 @@ use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
 use std::path::Path;
 
+use crate::bulk::BulkInserter;
 use crate::error::Result;
-use crate::statement::Statement;
+use crate::plan::QueryPlan;
+use crate::statement::{Bindable, FromRow, State, Statement};
+use crate::value::Value;
+use crate::vtab::{Module, VTab};
 
 /// A connection.
 pub struct Connection {
     raw: Raw,
     busy_callback: Option<Box<dyn FnMut(usize) -> bool + Send>>,
+    uri: bool,
     phantom: PhantomData<ffi::sqlite3>,
 }
 
 /// A thread-safe connection.
 pub struct ConnectionThreadSafe(Connection);
 
+/// A counter of a connection.
+///
+/// See the [documentation][1] of SQLite for further details.
+///
+/// [1]: https://www.sqlite.org/c3ref/c_dbstatus_options.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DbStatus {
+    /// The number of lookaside memory slots currently checked out.
+    LookasideUsed,
+    /// The approximate number of bytes of heap memory used by all pager caches.
+    CacheUsed,
+    /// The approximate number of bytes of heap memory used to store the schema.
+    SchemaUsed,
+    /// The approximate number of bytes of heap memory used by all prepared statements.
+    StatementUsed,
+    /// The number of malloc attempts satisfied using lookaside memory.
+    LookasideHit,
+    /// The number of malloc attempts that might have been satisfied using lookaside memory but
+    /// failed due to the amount of memory requested being larger than the slot size.
+    LookasideMissSize,
+    /// The number of malloc attempts that might have been satisfied using lookaside memory but
+    /// failed due to all lookaside memory already being in use.
+    LookasideMissFull,
+    /// The number of pager cache hits.
+    CacheHit,
+    /// The number of pager cache misses.
+    CacheMiss,
+    /// The number of dirty cache entries written to disk.
+    CacheWrite,
+    /// Whether all foreign key constraints have been resolved (zero) or not (one).
+    DeferredForeignKeys,
+    /// The same as `CacheUsed` but with the memory of shared caches divided evenly.
+    CacheUsedShared,
+    /// The number of dirty cache entries written to disk in the middle of a transaction.
+    CacheSpill,
+}
+
 /// Flags for opening a connection.
 #[derive(Clone, Copy, Debug)]
 pub struct OpenFlags(c_int);
@@ -24,19 +66,45 @@ struct Raw(*mut ffi::sqlite3);
 
 impl Connection {
     /// Open a read-write connection to a new or existing database.
+    ///
+    /// The connection does not accept URIs unless SQLite is compiled with `SQLITE_USE_URI`, and
+    /// therefore it can attach databases only for reading and writing; see `Connection::attach`.
     pub fn open<T: AsRef<Path>>(path: T) -> Result<Connection> {
         Connection::open_with_flags(path, OpenFlags::new().with_create().with_read_write())
     }
 
     /// Open a connection with specific flags.
+    #[inline]
     pub fn open_with_flags<T: AsRef<Path>>(path: T, flags: OpenFlags) -> Result<Connection> {
+        Connection::open_with_flags_and_optional_vfs(path, flags, None)
+    }
+
+    /// Open a connection with specific flags via a virtual file system registered by name.
+    #[inline]
+    pub fn open_with_flags_and_vfs<T: AsRef<Path>>(
+        path: T,
+        flags: OpenFlags,
+        vfs: &str,
+    ) -> Result<Connection> {
+        Connection::open_with_flags_and_optional_vfs(path, flags, Some(vfs))
+    }
+
+    fn open_with_flags_and_optional_vfs<T: AsRef<Path>>(
+        path: T,
+        flags: OpenFlags,
+        vfs: Option<&str>,
+    ) -> Result<Connection> {
+        let vfs = match vfs {
+            Some(vfs) => Some(str_to_cstr!(vfs)),
+            _ => None,
+        };
         let mut raw = std::ptr::null_mut();
         unsafe {
             let code = ffi::sqlite3_open_v2(
                 path_to_cstr!(path.as_ref()).as_ptr(),
                 &mut raw,
                 flags.0,
-                std::ptr::null(),
+                vfs.as_ref().map_or(std::ptr::null(), |vfs| vfs.as_ptr()),
             );
             match code {
                 ffi::SQLITE_OK => {}
@@ -47,19 +115,19 @@ impl Connection {
                     }
                     _ => {
                         ffi::sqlite3_close(raw);
-                        return Err(crate::error::Error {
-                            code: Some(code as isize),
-                            message: None,
-                        });
+                        return Err(crate::error::error(code));
                     }
                 },
             }
         }
-        Ok(Connection {
+        let connection = Connection {
             raw: Raw(raw),
             busy_callback: None,
+            uri: flags.0 & ffi::SQLITE_OPEN_URI != 0
+                || unsafe { ffi::sqlite3_compileoption_used(c"USE_URI".as_ptr()) != 0 },
             phantom: PhantomData,
-        })
+        };
+        Ok(connection)
     }
 
     /// Open a thread-safe read-write connection to a new or existing database.
@@ -82,6 +150,17 @@ impl Connection {
         Connection::open_with_flags(path, flags.with_full_mutex()).map(ConnectionThreadSafe)
     }
 
+    /// Open a thread-safe connection with specific flags via a virtual file system registered
+    /// by name.
+    pub fn open_thread_safe_with_flags_and_vfs<T: AsRef<Path>>(
+        path: T,
+        flags: OpenFlags,
+        vfs: &str,
+    ) -> Result<ConnectionThreadSafe> {
+        Connection::open_with_flags_and_vfs(path, flags.with_full_mutex(), vfs)
+            .map(ConnectionThreadSafe)
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn as_raw(&self) -> *mut ffi::sqlite3 {
@@ -140,6 +219,223 @@ impl Connection {
         crate::statement::new(self.raw.0, statement)
     }
 
+    /// Create an inserter of many rows into a table.
+    ///
+    /// The table name may be qualified with a schema as in `main.users`, in which case the part
+    /// before the first dot is taken as the schema. The schema, table, and column names are
+    /// quoted as identifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite::Value;
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name TEXT, age REAL)");
+    /// let columns = ["id", "name", "age"];
+    /// let mut inserter = connection.bulk_insert("users", &columns)?.with_batch_size(100);
+    /// for id in 0..1000 {
+    ///     inserter.insert((id, format!("user {id}"), 42.0))?;
+    /// }
+    /// inserter.insert(&[Value::Integer(1000), Value::Null, Value::Null][..])?;
+    /// assert_eq!(inserter.finish()?, 1001);
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    #[inline]
+    pub fn bulk_insert<T: AsRef<str>>(
+        &self,
+        table: &str,
+        columns: &[T],
+    ) -> Result<BulkInserter<'_>> {
+        crate::bulk::new(self, table, columns)
+    }
+
+    /// Register a module of virtual tables.
+    ///
+    /// See `VTab` for an example.
+    #[inline]
+    pub fn create_module<T: VTab>(&self, name: &str, module: Module<T>) -> Result<()> {
+        crate::vtab::create(self, name, module)
+    }
+
+    /// Register a table-valued function.
+    ///
+    /// The function is exposed as an eponymous virtual table with the given columns, which are
+    /// column definitions as in `CREATE TABLE`. Columns marked as `HIDDEN` are the parameters of
+    /// the function: they receive the arguments, which are passed to the closure in the same
+    /// order, with absent arguments being `NULL`, and the rest receive the values of the rows
+    /// produced by the closure. Missing values are `NULL`. At most 31 parameters are supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite::Value;
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// let columns = ["value TEXT", "input HIDDEN", "separator HIDDEN"];
+    /// connection.create_table_function("split", &columns, |arguments: &[Value]| {
+    ///     let input = String::try_from(arguments[0].clone()).unwrap_or_default();
+    ///     let separator = String::try_from(arguments[1].clone()).unwrap_or(",".into());
+    ///     input
+    ///         .split(&separator)
+    ///         .map(|value| vec![Value::String(value.into())])
+    ///         .collect::<Vec<_>>()
+    /// })?;
+    /// let query = "SELECT value FROM split('a,b,c', ',')";
+    /// let values = connection.query_all::<(String,), _, _>(query, ())?;
+    /// assert_eq!(values, [("a".into(),), ("b".into(),), ("c".into(),)]);
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    #[inline]
+    pub fn create_table_function<F, I>(
+        &self,
+        name: &str,
+        columns: &[&str],
+        function: F,
+    ) -> Result<()>
+    where
+        F: Fn(&[Value]) -> I + Send + Sync + 'static,
+        I: IntoIterator<Item = Vec<Value>> + 'static,
+    {
+        crate::vtab::create_function(self, name, columns, function)
+    }
+
+    /// Register the `rarray` table-valued function for reading arrays bound to parameters.
+    ///
+    /// See `Statement::bind` for an example.
+    #[inline]
+    pub fn enable_rarray(&self) -> Result<()> {
+        crate::array::create(self)
+    }
+
+    /// Return the query plan of a statement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name STRING)");
+    /// let plan = connection.query_plan("SELECT * FROM users WHERE id = 42")?;
+    /// assert!(plan.uses_full_scan());
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    #[inline]
+    pub fn query_plan<T: AsRef<str>>(&self, statement: T) -> Result<QueryPlan> {
+        crate::plan::new(self, statement)
+    }
+
+    /// Execute a statement expected to return exactly one row and process the row.
+    ///
+    /// An error is returned if the statement returns no rows or more than one row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name STRING)");
+    /// # connection.execute("INSERT INTO users VALUES (1, 'Alice')");
+    /// let query = "SELECT name FROM users WHERE id = ?";
+    /// let name = connection.query_row(query, (1, 1), |statement| statement.read::<String, _>(0))?;
+    /// assert_eq!(name, "Alice");
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    pub fn query_row<T, U, F, V>(&self, statement: T, parameters: U, callback: F) -> Result<V>
+    where
+        T: AsRef<str>,
+        U: Bindable,
+        F: FnOnce(&Statement) -> Result<V>,
+    {
+        let mut statement = self.prepare(statement)?;
+        statement.bind(parameters)?;
+        if statement.next()? == State::Done {
+            raise!("the query returned no rows");
+        }
+        let value = callback(&statement)?;
+        if statement.next()? == State::Row {
+            raise!("the query returned more than one row");
+        }
+        Ok(value)
+    }
+
+    /// Execute a statement and process each of the resulting rows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name STRING)");
+    /// # connection.execute("INSERT INTO users VALUES (1, 'Alice')");
+    /// let query = "SELECT name FROM users";
+    /// let names = connection.query_map(query, (), |statement| statement.read::<String, _>(0))?;
+    /// assert_eq!(names, vec!["Alice"]);
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    pub fn query_map<T, U, F, V>(
+        &self,
+        statement: T,
+        parameters: U,
+        mut callback: F,
+    ) -> Result<Vec<V>>
+    where
+        T: AsRef<str>,
+        U: Bindable,
+        F: FnMut(&Statement) -> Result<V>,
+    {
+        let mut statement = self.prepare(statement)?;
+        statement.bind(parameters)?;
+        let mut values = Vec::new();
+        while let State::Row = statement.next()? {
+            values.push(callback(&statement)?);
+        }
+        Ok(values)
+    }
+
+    /// Execute a statement expected to return exactly one row and read the row.
+    ///
+    /// An error is returned if the statement returns no rows or more than one row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name STRING)");
+    /// # connection.execute("INSERT INTO users VALUES (1, 'Alice')");
+    /// let query = "SELECT id, name FROM users WHERE id = :id";
+    /// let (id, name) = connection.query_one::<(i64, String), _, _>(query, (":id", 1))?;
+    /// assert_eq!((id, name.as_str()), (1, "Alice"));
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    #[inline]
+    pub fn query_one<T, U, V>(&self, statement: U, parameters: V) -> Result<T>
+    where
+        T: FromRow,
+        U: AsRef<str>,
+        V: Bindable,
+    {
+        self.query_row(statement, parameters, T::from_row)
+    }
+
+    /// Execute a statement and read all the resulting rows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name STRING)");
+    /// # connection.execute("INSERT INTO users VALUES (1, 'Alice')");
+    /// let query = "SELECT id, name FROM users";
+    /// let users = connection.query_all::<(i64, String), _, _>(query, ())?;
+    /// assert_eq!(users, vec![(1, "Alice".into())]);
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    #[inline]
+    pub fn query_all<T, U, V>(&self, statement: U, parameters: V) -> Result<Vec<T>>
+    where
+        T: FromRow,
+        U: AsRef<str>,
+        V: Bindable,
+    {
+        self.query_map(statement, parameters, T::from_row)
+    }
+
     /// Return the number of rows inserted, updated, or deleted by the most recent INSERT, UPDATE,
     /// or DELETE statement.
     #[inline]
@@ -153,6 +449,150 @@ impl Connection {
     pub fn total_change_count(&self) -> usize {
         unsafe { ffi::sqlite3_total_changes(self.raw.0) as usize }
     }
+
+    /// Return the current and highest values of a counter.
+    ///
+    /// If `reset` is `true`, the highest value is set to the current one after being read. Some
+    /// counters do not track the highest value, in which case it is zero.
+    pub fn status(&self, counter: DbStatus, reset: bool) -> Result<(usize, usize)> {
+        let counter = match counter {
+            DbStatus::LookasideUsed => ffi::SQLITE_DBSTATUS_LOOKASIDE_USED,
+            DbStatus::CacheUsed => ffi::SQLITE_DBSTATUS_CACHE_USED,
+            DbStatus::SchemaUsed => ffi::SQLITE_DBSTATUS_SCHEMA_USED,
+            DbStatus::StatementUsed => ffi::SQLITE_DBSTATUS_STMT_USED,
+            DbStatus::LookasideHit => ffi::SQLITE_DBSTATUS_LOOKASIDE_HIT,
+            DbStatus::LookasideMissSize => ffi::SQLITE_DBSTATUS_LOOKASIDE_MISS_SIZE,
+            DbStatus::LookasideMissFull => ffi::SQLITE_DBSTATUS_LOOKASIDE_MISS_FULL,
+            DbStatus::CacheHit => ffi::SQLITE_DBSTATUS_CACHE_HIT,
+            DbStatus::CacheMiss => ffi::SQLITE_DBSTATUS_CACHE_MISS,
+            DbStatus::CacheWrite => ffi::SQLITE_DBSTATUS_CACHE_WRITE,
+            DbStatus::DeferredForeignKeys => ffi::SQLITE_DBSTATUS_DEFERRED_FKS,
+            DbStatus::CacheUsedShared => ffi::SQLITE_DBSTATUS_CACHE_USED_SHARED,
+            DbStatus::CacheSpill => ffi::SQLITE_DBSTATUS_CACHE_SPILL,
+        };
+        let mut current: c_int = 0;
+        let mut highest: c_int = 0;
+        unsafe {
+            ok!(ffi::sqlite3_db_status(
+                self.raw.0,
+                counter,
+                &mut current,
+                &mut highest,
+                c_int::from(reset),
+            ));
+        }
+        Ok((current as usize, highest as usize))
+    }
+}
+
+impl Connection {
+    /// Attach a database under a schema name.
+    ///
+    /// The flags are interpreted as when opening a connection; however, only the access mode
+    /// is taken into account. A mode other than reading, writing, and creating, such as reading
+    /// only, requires the connection to accept URIs, and so does a path given as a URI, which is
+    /// signaled by `OpenFlags::with_uri`. A connection accepts URIs if it has been opened with
+    /// `OpenFlags::with_uri` or if SQLite is compiled with `SQLITE_USE_URI`, which is not the
+    /// case for the bundled library; in particular, a connection opened via `sqlite::open` might
+    /// not accept them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite::{Connection, OpenFlags};
+    ///
+    /// let connection = sqlite::open(":memory:")?;
+    /// let flags = OpenFlags::new().with_create().with_read_write();
+    /// connection.attach(":memory:", "cache", flags)?;
+    /// connection.execute("CREATE TABLE cache.users (name TEXT)")?;
+    /// assert_eq!(connection.database_names()?, ["main", "cache"]);
+    /// connection.detach("cache")?;
+    /// assert_eq!(connection.database_names()?, ["main"]);
+    ///
+    /// let flags = OpenFlags::new().with_create().with_read_write().with_uri();
+    /// let connection = Connection::open_with_flags(":memory:", flags)?;
+    /// connection.attach(":memory:", "archive", OpenFlags::new().with_read_only())?;
+    /// assert!(connection.is_readonly("archive")?);
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    pub fn attach<T: AsRef<Path>>(&self, path: T, name: &str, flags: OpenFlags) -> Result<()> {
+        validate(name)?;
+        let path = path.as_ref();
+        let path = if flags.0 & ffi::SQLITE_OPEN_URI != 0 {
+            if !self.uri {
+                raise!("the connection does not accept URIs");
+            }
+            path_to_string(path)?
+        } else {
+            let mode = if flags.0 & ffi::SQLITE_OPEN_READONLY != 0 {
+                "ro"
+            } else if flags.0 & ffi::SQLITE_OPEN_CREATE == 0 {
+                "rw"
+            } else {
+                ""
+            };
+            match mode {
+                "" if !self.uri => path_to_string(path)?,
+                _ if !self.uri => raise!("the connection does not accept URIs"),
+                _ => {
+                    let mut uri = path_to_uri(&path_to_string(path)?);
+                    if !mode.is_empty() {
+                        uri.push_str("?mode=");
+                        uri.push_str(mode);
+                    }
+                    uri
+                }
+            }
+        };
+        let mut statement = self.prepare("ATTACH DATABASE ? AS ?")?;
+        statement.bind((1, path.as_str()))?;
+        statement.bind((2, name))?;
+        while let State::Row = statement.next()? {}
+        Ok(())
+    }
+
+    /// Detach a database previously attached under a schema name.
+    pub fn detach(&self, name: &str) -> Result<()> {
+        validate(name)?;
+        let mut statement = self.prepare("DETACH DATABASE ?")?;
+        statement.bind((1, name))?;
+        while let State::Row = statement.next()? {}
+        Ok(())
+    }
+
+    /// Return the schema names of the databases, starting with `main`.
+    pub fn database_names(&self) -> Result<Vec<String>> {
+        let mut names = Vec::new();
+        let mut statement = self.prepare("PRAGMA database_list")?;
+        while let State::Row = statement.next()? {
+            names.push(statement.read::<String, _>("name")?);
+        }
+        Ok(names)
+    }
+
+    /// Return the path of the database with a schema name.
+    ///
+    /// `None` is returned for temporary and in-memory databases.
+    pub fn db_filename(&self, name: &str) -> Result<Option<String>> {
+        unsafe {
+            let path = ffi::sqlite3_db_filename(self.raw.0, str_to_cstr!(name).as_ptr());
+            if path.is_null() {
+                raise!("the database does not exist");
+            }
+            match c_str_to_string!(path) {
+                path if path.is_empty() => Ok(None),
+                path => Ok(Some(path)),
+            }
+        }
+    }
+
+    /// Check if the database with a schema name is read-only.
+    pub fn is_readonly(&self, name: &str) -> Result<bool> {
+        match unsafe { ffi::sqlite3_db_readonly(self.raw.0, str_to_cstr!(name).as_ptr()) } {
+            -1 => raise!("the database does not exist"),
+            code => Ok(code == 1),
+        }
+    }
 }
 
 impl Connection {
@@ -249,48 +689,6 @@ impl Connection {
         }
         Ok(())
     }
-
-    /// Serializes the SQLite database to an in-memory buffer.
-    /// This function allows you to serialize an SQLite database to an in-memory buffer.
-    /// The output is the same bytes as if the database were to be written to disk.
-    #[cfg(feature = "serialize")]
-    #[inline]
-    pub async fn serialize_to_buffer(&mut self) -> Result<Vec<u8>> {
-        let mut res = Vec::new();
-        unsafe {
-            let mut size: ffi::sqlite3_int64 = 0;
-            let data_ptr =
-                ffi::sqlite3_serialize(self.raw.0, std::ptr::null(), &mut size as *mut _, 0);
-
-            if data_ptr == std::ptr::null_mut() {
-                raise!("Could not create database buffer!");
-            }
-
-            for _ in 0..size {
-                res.push(data_ptr.read() as u8)
-            }
-        }
-        Ok(res)
-    }
-
-    #[cfg(feature = "serialize")]
-    #[inline]
-    pub fn deserialize_to_readonly_db(&self, data: &[u8]) -> Result<()> {
-        unsafe {
-            ok!(
-                self.raw.0,
-                ffi::sqlite3_deserialize(
-                    self.raw.0,
-                    std::ptr::null(),
-                    data.as_ptr() as *mut u8,
-                    data.len() as i64,
-                    data.len() as i64,
-                    ffi::SQLITE_DESERIALIZE_READONLY as u32
-                )
-            );
-        }
-        Ok(())
-    }
 }
 
 impl Connection {
@@ -415,6 +813,55 @@ unsafe impl Sync for ConnectionThreadSafe {}
 
 unsafe impl Send for Raw {}
 
+fn encode(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+fn path_to_string(path: &Path) -> Result<String> {
+    match path.to_str() {
+        Some(path) => Ok(path.into()),
+        _ => raise!("failed to process a path"),
+    }
+}
+
+// Convert a path into a URI. In-memory and temporary databases have no path to encode, and
+// absolute paths are given an empty authority so that a leading `//` is not taken for one. On
+// Windows, backslashes are replaced with slashes, and drive letters are preceded by a slash.
+fn path_to_uri(path: &str) -> String {
+    let path = if cfg!(windows) {
+        path.replace('\\', "/")
+    } else {
+        path.into()
+    };
+    match path.as_bytes() {
+        b":memory:" | b"" => format!("file:{path}"),
+        [b'/', ..] => format!("file://{}", encode(&path)),
+        [letter, b':', b'/', ..] if cfg!(windows) && letter.is_ascii_alphabetic() => {
+            format!("file:///{}{}", &path[..2], encode(&path[2..]))
+        }
+        _ => format!("file:{}", encode(&path)),
+    }
+}
+
+fn validate(name: &str) -> Result<()> {
+    if name.is_empty() || name.contains('\0') {
+        raise!("the schema name is invalid");
+    }
+    if name.eq_ignore_ascii_case("main") || name.eq_ignore_ascii_case("temp") {
+        raise!("the schema name is reserved");
+    }
+    Ok(())
+}
+
 extern "C" fn busy_callback<F>(callback: *mut c_void, attempts: c_int) -> c_int
 where
     F: FnMut(usize) -> bool,
@@ -0,0 +1,447 @@
+use std::borrow::Cow;
+use std::io::{Read, Write};
+
+use ::csv::{ReaderBuilder, StringRecord, WriterBuilder};
+
+use crate::bulk::{quote, quote_table, DEFAULT_BATCH_SIZE};
+use crate::connection::Connection;
+use crate::error::{failure, Error, Result};
+use crate::value::{Type, Value, ValueRef};
+
+macro_rules! csv(
+    ($result:expr) => (
+        match $result {
+            Ok(result) => result,
+            Err(error) => raise!("failed to process CSV ({error})"),
+        }
+    );
+);
+
+/// The default number of records used for inferring column types.
+pub const DEFAULT_INFERENCE_SIZE: usize = 100;
+
+/// An encoding of binary data in CSV.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BlobEncoding {
+    /// Hexadecimal digits in lowercase.
+    #[default]
+    Hex,
+    /// Base64 with padding.
+    Base64,
+    /// UTF-8 with invalid sequences replaced.
+    Text,
+}
+
+/// Options for exporting to CSV.
+#[derive(Clone, Debug)]
+pub struct CsvExportOptions {
+    delimiter: u8,
+    header: bool,
+    null: String,
+    blob: BlobEncoding,
+}
+
+/// Options for importing from CSV.
+#[derive(Clone, Debug)]
+pub struct CsvImportOptions {
+    delimiter: u8,
+    header: bool,
+    null: String,
+    create: bool,
+    batch_size: usize,
+    inference_size: usize,
+}
+
+/// A summary of importing from CSV.
+#[derive(Debug)]
+pub struct CsvImport {
+    inserted: usize,
+    errors: Vec<(u64, Error)>,
+}
+
+impl Connection {
+    /// Execute a query and write the resulting rows to CSV.
+    ///
+    /// The header consists of the column names of the query. The function returns the number of
+    /// written rows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite::CsvExportOptions;
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name TEXT, photo BLOB)");
+    /// # connection.execute("INSERT INTO users VALUES (1, 'Alice', X'4269'), (2, NULL, NULL)");
+    /// let mut buffer = Vec::new();
+    /// let options = CsvExportOptions::new().with_null("NULL");
+    /// connection.export_csv("SELECT * FROM users", &mut buffer, &options)?;
+    /// assert_eq!(buffer, b"id,name,photo\n1,Alice,4269\n2,NULL,NULL\n");
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    pub fn export_csv<T: AsRef<str>, W: Write>(
+        &self,
+        query: T,
+        writer: W,
+        options: &CsvExportOptions,
+    ) -> Result<usize> {
+        let mut statement = self.prepare(query)?;
+        let mut writer = WriterBuilder::new()
+            .delimiter(options.delimiter)
+            .from_writer(writer);
+        if options.header {
+            csv!(writer.write_record(statement.column_names()));
+        }
+        let mut count = 0;
+        let mut rows = statement.rows_ref();
+        while let Some(row) = rows.next()? {
+            let mut record = Vec::with_capacity(row.column_count());
+            for index in 0..row.column_count() {
+                record.push(encode(row.read_ref(index)?, options));
+            }
+            csv!(writer.write_record(&record));
+            count += 1;
+        }
+        csv!(writer.flush());
+        Ok(count)
+    }
+
+    /// Read rows from CSV and insert them into a table.
+    ///
+    /// The columns are taken from the header or, if there is no header, from the table; see
+    /// `CsvImportOptions::without_header`. Fields equal to the NULL string are inserted as NULL,
+    /// and the rest are inserted as text, which is then converted according to the type affinity of
+    /// the columns. Records that fail to be parsed or inserted are skipped and reported together
+    /// with their line numbers. The table name may be qualified with a schema; see
+    /// `Connection::bulk_insert`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite::CsvImportOptions;
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// let data = "id,name,age\n1,Alice,42.69\n2,Bob,\n3\n";
+    /// let options = CsvImportOptions::new().with_create();
+    /// let summary = connection.import_csv(data.as_bytes(), "users", &options)?;
+    /// assert_eq!(summary.inserted(), 2);
+    /// assert_eq!(summary.errors()[0].0, 4);
+    /// let query = "SELECT typeof(id), typeof(age) FROM users WHERE id = 1";
+    /// let types = connection.query_one::<(String, String), _, _>(query, ())?;
+    /// assert_eq!(types, ("integer".into(), "real".into()));
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    pub fn import_csv<R: Read>(
+        &self,
+        reader: R,
+        table: &str,
+        options: &CsvImportOptions,
+    ) -> Result<CsvImport> {
+        let mut reader = ReaderBuilder::new()
+            .delimiter(options.delimiter)
+            .has_headers(options.header)
+            .from_reader(reader);
+        let header = if options.header {
+            Some(csv!(reader.headers()).clone())
+        } else {
+            None
+        };
+        let buffer = if options.create {
+            reader
+                .records()
+                .take(options.inference_size)
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+        let columns = if let Some(header) = header {
+            header.iter().map(String::from).collect::<Vec<_>>()
+        } else if options.create {
+            let count = buffer
+                .iter()
+                .filter_map(|record| record.as_ref().ok())
+                .map(StringRecord::len)
+                .max()
+                .unwrap_or(0);
+            (1..=count).map(|index| format!("c{index}")).collect()
+        } else {
+            let query = format!("SELECT * FROM {} LIMIT 0", quote_table(table));
+            self.prepare(query)?.column_names().to_vec()
+        };
+        if options.create {
+            let definitions = columns
+                .iter()
+                .zip(infer(columns.len(), &buffer, options))
+                .map(|(column, kind)| format!("{} {kind}", quote(column)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            self.execute(format!(
+                "CREATE TABLE IF NOT EXISTS {} ({definitions})",
+                quote_table(table),
+            ))?;
+        }
+        let mut inserter = self
+            .bulk_insert(table, &columns)?
+            .with_batch_size(options.batch_size);
+        let mut errors = Vec::new();
+        let mut line = 0;
+        for record in buffer.into_iter().chain(reader.records()) {
+            let record = match record {
+                Ok(record) => record,
+                Err(error) if error.is_io_error() => raise!("failed to read CSV ({error})"),
+                Err(error) => {
+                    line = error
+                        .position()
+                        .map_or(line + 1, |position| position.line());
+                    errors.push((line, failure(format!("failed to parse CSV ({error})"))));
+                    continue;
+                }
+            };
+            line = record
+                .position()
+                .map_or(line + 1, |position| position.line());
+            if record.len() != columns.len() {
+                let message = format!(
+                    "expected {} fields but found {}",
+                    columns.len(),
+                    record.len(),
+                );
+                errors.push((line, failure(message)));
+                continue;
+            }
+            let values = record
+                .iter()
+                .map(|field| {
+                    if field == options.null {
+                        Value::Null
+                    } else {
+                        Value::String(field.into())
+                    }
+                })
+                .collect::<Vec<_>>();
+            if let Err(error) = inserter.insert(&values[..]) {
+                errors.push((line, error));
+            }
+        }
+        Ok(CsvImport {
+            inserted: inserter.finish()?,
+            errors,
+        })
+    }
+}
+
+impl CsvExportOptions {
+    /// Create options with a comma as the delimiter, a header, empty fields for NULL, and
+    /// hexadecimal digits for binary data.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            delimiter: b',',
+            header: true,
+            null: String::new(),
+            blob: BlobEncoding::default(),
+        }
+    }
+
+    /// Set the delimiter.
+    #[inline]
+    pub fn with_delimiter(mut self, value: u8) -> Self {
+        self.delimiter = value;
+        self
+    }
+
+    /// Omit the header.
+    #[inline]
+    pub fn without_header(mut self) -> Self {
+        self.header = false;
+        self
+    }
+
+    /// Set the string written for NULL.
+    #[inline]
+    pub fn with_null<T: Into<String>>(mut self, value: T) -> Self {
+        self.null = value.into();
+        self
+    }
+
+    /// Set the encoding of binary data.
+    #[inline]
+    pub fn with_blob_encoding(mut self, value: BlobEncoding) -> Self {
+        self.blob = value;
+        self
+    }
+}
+
+impl Default for CsvExportOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CsvImportOptions {
+    /// Create options with a comma as the delimiter, a header, and empty fields for NULL.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            delimiter: b',',
+            header: true,
+            null: String::new(),
+            create: false,
+            batch_size: DEFAULT_BATCH_SIZE,
+            inference_size: DEFAULT_INFERENCE_SIZE,
+        }
+    }
+
+    /// Set the delimiter.
+    #[inline]
+    pub fn with_delimiter(mut self, value: u8) -> Self {
+        self.delimiter = value;
+        self
+    }
+
+    /// Treat the first record as data.
+    ///
+    /// The columns are named `c1`, `c2`, and so on if the table is to be created.
+    #[inline]
+    pub fn without_header(mut self) -> Self {
+        self.header = false;
+        self
+    }
+
+    /// Set the string read as NULL.
+    #[inline]
+    pub fn with_null<T: Into<String>>(mut self, value: T) -> Self {
+        self.null = value.into();
+        self
+    }
+
+    /// Create the table if it does not already exist.
+    ///
+    /// The type of each column is inferred from the first records as `INTEGER`, `REAL`, or
+    /// `TEXT`; see `with_inference_size`.
+    #[inline]
+    pub fn with_create(mut self) -> Self {
+        self.create = true;
+        self
+    }
+
+    /// Set the number of rows inserted per transaction.
+    ///
+    /// See `BulkInserter::with_batch_size` for further details.
+    #[inline]
+    pub fn with_batch_size(mut self, value: usize) -> Self {
+        self.batch_size = value;
+        self
+    }
+
+    /// Set the number of records used for inferring column types.
+    ///
+    /// The default value is `DEFAULT_INFERENCE_SIZE`.
+    #[inline]
+    pub fn with_inference_size(mut self, value: usize) -> Self {
+        self.inference_size = value;
+        self
+    }
+}
+
+impl Default for CsvImportOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CsvImport {
+    /// Return the number of inserted rows.
+    #[inline]
+    pub fn inserted(&self) -> usize {
+        self.inserted
+    }
+
+    /// Return the errors together with the line numbers of the corresponding records.
+    #[inline]
+    pub fn errors(&self) -> &[(u64, Error)] {
+        &self.errors
+    }
+}
+
+fn encode<'l>(value: ValueRef<'l>, options: &'l CsvExportOptions) -> Cow<'l, [u8]> {
+    match value {
+        ValueRef::Blob(value) => match options.blob {
+            BlobEncoding::Hex => Cow::Owned(encode_hex(value)),
+            BlobEncoding::Base64 => Cow::Owned(encode_base64(value)),
+            BlobEncoding::Text => Cow::Owned(String::from_utf8_lossy(value).into_owned().into()),
+        },
+        ValueRef::Real(value) => Cow::Owned(format!("{value:?}").into_bytes()),
+        ValueRef::Integer(value) => Cow::Owned(value.to_string().into_bytes()),
+        ValueRef::Text(value) => Cow::Borrowed(value.as_bytes()),
+        ValueRef::Null => Cow::Borrowed(options.null.as_bytes()),
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> Vec<u8> {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut result = Vec::with_capacity(2 * bytes.len());
+    for byte in bytes {
+        result.push(DIGITS[(byte >> 4) as usize]);
+        result.push(DIGITS[(byte & 0x0f) as usize]);
+    }
+    result
+}
+
+fn encode_base64(bytes: &[u8]) -> Vec<u8> {
+    const DIGITS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut result = Vec::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let value = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |value, (index, &byte)| {
+                value | (byte as u32) << (16 - 8 * index)
+            });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                result.push(DIGITS[(value >> (18 - 6 * index) & 0x3f) as usize]);
+            } else {
+                result.push(b'=');
+            }
+        }
+    }
+    result
+}
+
+fn infer(
+    count: usize,
+    records: &[::csv::Result<StringRecord>],
+    options: &CsvImportOptions,
+) -> Vec<&'static str> {
+    let mut types = vec![None::<Type>; count];
+    for record in records.iter().filter_map(|record| record.as_ref().ok()) {
+        for (kind, field) in types.iter_mut().zip(record.iter()) {
+            if field == options.null {
+                continue;
+            }
+            let found = if field.trim().parse::<i64>().is_ok() {
+                Type::Integer
+            } else if field.trim().parse::<f64>().is_ok() {
+                Type::Float
+            } else {
+                Type::String
+            };
+            *kind = match (*kind, found) {
+                (None, found) => Some(found),
+                (Some(Type::Integer), Type::Float) => Some(Type::Float),
+                (Some(Type::Float), Type::Integer) => Some(Type::Float),
+                (Some(kind), found) if kind == found => Some(kind),
+                _ => Some(Type::String),
+            };
+        }
+    }
+    types
+        .into_iter()
+        .map(|kind| match kind {
+            Some(Type::Integer) => "INTEGER",
+            Some(Type::Float) => "REAL",
+            _ => "TEXT",
+        })
+        .collect()
+}
@@ -1,11 +1,12 @@
+use std::cell::{OnceCell, RefCell};
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::ops::{Deref, Index};
 use std::rc::Rc;
 
 use crate::error::{Error, Result};
-use crate::statement::{Bindable, State, Statement};
-use crate::value::Value;
+use crate::statement::{Bindable, ColumnIndex, ReadableWithIndex, State, Statement};
+use crate::value::{Value, ValueRef};
 
 /// An iterator for a prepared statement.
 pub struct Cursor<'l, 'm> {
@@ -21,12 +22,30 @@ pub struct CursorWithOwnership<'l> {
     poisoned: bool,
 }
 
+/// A cursor for a prepared statement whose rows borrow from the buffers of SQLite.
+pub struct RowsRef<'l, 'm> {
+    statement: &'m mut Statement<'l>,
+    poisoned: bool,
+}
+
+/// A row borrowing from the buffers of SQLite.
+#[derive(Clone, Copy)]
+pub struct RowRef<'r, 'l> {
+    statement: &'r Statement<'l>,
+}
+
 /// A row.
 #[derive(Debug)]
 pub struct Row {
-    column_names: Rc<Vec<String>>,
+    pub(crate) column_names: Rc<Vec<String>>,
     column_mapping: Rc<HashMap<String, usize>>,
-    values: Vec<Value>,
+    pub(crate) values: Vec<OnceCell<Value>>,
+    // The statement missing values are read from, which is only set by `Statement::for_each_row`
+    // for the duration of a callback.
+    statement: *const Statement<'static>,
+    // The first error of reading a value via indexing or iteration, which is reported by
+    // `Statement::for_each_row` after the callback.
+    failure: RefCell<Option<Error>>,
 }
 
 /// A type suitable for indexing columns in a row.
@@ -110,7 +129,9 @@ macro_rules! implement(
                         value.map(|values| Ok(Row {
                             column_names: self.statement.column_names.clone(),
                             column_mapping: self.statement.column_mapping(),
-                            values,
+                            values: values.into_iter().map(OnceCell::from).collect(),
+                            statement: std::ptr::null(),
+                            failure: RefCell::new(None),
                         }))
                     }
                     Err(error) => {
@@ -133,6 +154,79 @@ impl<'l> From<CursorWithOwnership<'l>> for Statement<'l> {
     }
 }
 
+impl<'l> RowsRef<'l, '_> {
+    /// Bind values to parameters.
+    ///
+    /// In case of integer indices, the first parameter has index 1. See `Statement::bind` for
+    /// further details.
+    pub fn bind<T: Bindable>(self, value: T) -> Result<Self> {
+        let cursor = self.reset()?;
+        cursor.statement.bind(value)?;
+        Ok(cursor)
+    }
+
+    /// Reset the internal state.
+    pub fn reset(mut self) -> Result<Self> {
+        self.statement.reset()?;
+        self.poisoned = false;
+        Ok(self)
+    }
+
+    /// Advance to the next row.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<Option<RowRef<'_, 'l>>> {
+        if self.poisoned {
+            return Ok(None);
+        }
+        match self.statement.next() {
+            Ok(State::Row) => Ok(Some(RowRef {
+                statement: self.statement,
+            })),
+            Ok(State::Done) => Ok(None),
+            Err(error) => {
+                self.poisoned = true;
+                Err(error)
+            }
+        }
+    }
+}
+
+impl<'r> RowRef<'r, '_> {
+    /// Return the number of columns.
+    #[inline]
+    pub fn column_count(&self) -> usize {
+        self.statement.column_count()
+    }
+
+    /// Return the name of a column.
+    ///
+    /// In case of integer indices, the first column has index 0.
+    #[inline]
+    pub fn column_name<T: ColumnIndex>(&self, index: T) -> Result<&'r str> {
+        self.statement.column_name(index)
+    }
+
+    /// Read the value in a column.
+    ///
+    /// In case of integer indices, the first column has index 0.
+    #[inline]
+    pub fn read<T, U>(&self, index: U) -> Result<T>
+    where
+        T: ReadableWithIndex,
+        U: ColumnIndex,
+    {
+        self.statement.read(index)
+    }
+
+    /// Read the value in a column without copying it.
+    ///
+    /// In case of integer indices, the first column has index 0.
+    #[inline]
+    pub fn read_ref<T: ColumnIndex>(&self, index: T) -> Result<ValueRef<'r>> {
+        self.statement.read_ref(index)
+    }
+}
+
 impl Row {
     /// Check if the row contains a column.
     ///
@@ -171,7 +265,8 @@ impl Row {
         U: RowIndex,
     {
         let index = column.index(self);
-        std::mem::take(&mut self.values[index])
+        let cell = std::mem::replace(&mut self.values[index], OnceCell::from(Value::Null));
+        cell.into_inner().unwrap_or_default()
     }
 
     /// Try to read the value in a column.
@@ -186,15 +281,42 @@ impl Row {
         if !column.contains(self) {
             raise!("the index is out of range ({column})");
         }
-        T::try_from(&self.values[column.index(self)])
+        T::try_from(self.value(column.index(self))?)
+    }
+
+    // Return the value in a column, reading it from the statement if needed.
+    pub(crate) fn value(&self, index: usize) -> Result<&Value> {
+        let cell = &self.values[index];
+        if cell.get().is_none() {
+            let value = if self.statement.is_null() {
+                Value::Null
+            } else {
+                unsafe { (*self.statement).read(index)? }
+            };
+            let _ = cell.set(value);
+        }
+        Ok(cell.get().unwrap())
+    }
+
+    // Return the value in a column, recording the error and falling back to null if it can not
+    // be read.
+    fn value_or_null(&self, index: usize) -> &Value {
+        if let Err(error) = self.value(index) {
+            self.failure.borrow_mut().get_or_insert(error);
+            let _ = self.values[index].set(Value::Null);
+        }
+        self.values[index].get().unwrap()
     }
 
     /// Iterate over the names and values.
+    ///
+    /// Within `Statement::for_each_row`, columns that can not be read are null, and the error is
+    /// returned by `Statement::for_each_row` once the callback returns.
     pub fn iter(&self) -> impl Iterator<Item = (&'_ str, &'_ Value)> + use<'_> {
         self.column_names.iter().map(|column_name| {
             (
                 column_name.as_str(),
-                &self.values[self.column_mapping[column_name]],
+                self.value_or_null(self.column_mapping[column_name]),
             )
         })
     }
@@ -203,7 +325,11 @@ impl Row {
 impl From<Row> for Vec<Value> {
     #[inline]
     fn from(row: Row) -> Self {
+        // Owned rows are read in full.
         row.values
+            .into_iter()
+            .map(|value| value.into_inner().unwrap_or_default())
+            .collect()
     }
 }
 
@@ -213,8 +339,12 @@ where
 {
     type Output = Value;
 
+    /// Return the value in a column.
+    ///
+    /// Within `Statement::for_each_row`, a column that can not be read is null, and the error is
+    /// returned by `Statement::for_each_row` once the callback returns.
     fn index(&self, index: T) -> &Value {
-        &self.values[index.index(self)]
+        self.value_or_null(index.index(self))
     }
 }
 
@@ -244,6 +374,37 @@ impl RowIndex for usize {
     }
 }
 
+pub fn for_each_row<F>(statement: &mut Statement<'_>, mut callback: F) -> Result<()>
+where
+    F: FnMut(&Row) -> Result<()>,
+{
+    let mut row = Row {
+        column_names: statement.column_names.clone(),
+        column_mapping: statement.column_mapping(),
+        values: vec![OnceCell::new(); statement.column_count()],
+        statement: std::ptr::null(),
+        failure: RefCell::new(None),
+    };
+    while let State::Row = statement.next()? {
+        row.values.iter_mut().for_each(|value| drop(value.take()));
+        row.statement = (&*statement as *const Statement).cast();
+        let result = callback(&row);
+        row.statement = std::ptr::null();
+        result?;
+        if let Some(error) = row.failure.take() {
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+pub fn new_ref<'l, 'm>(statement: &'m mut Statement<'l>) -> RowsRef<'l, 'm> {
+    RowsRef {
+        statement,
+        poisoned: false,
+    }
+}
+
 pub fn new<'l, 'm>(statement: &'m mut Statement<'l>) -> Cursor<'l, 'm> {
     Cursor {
         column_count: statement.column_count(),
@@ -0,0 +1,312 @@
+use crate::error::Result;
+use crate::value::Value;
+
+const NANOSECONDS_PER_SECOND: i64 = 1_000_000_000;
+const SECONDS_PER_DAY: i64 = 86_400;
+// The Julian day of 1970-01-01 00:00:00 expressed in milliseconds.
+const UNIX_EPOCH_IN_JULIAN_MILLISECONDS: i64 = 210_866_760_000_000;
+// The Julian day of 9999-12-31 23:59:59.999, which is the largest one supported by SQLite.
+const MAX_JULIAN_DAY: f64 = 5_373_484.499_999;
+
+/// A point in time in one of the formats understood by the date and time functions of SQLite.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timestamp {
+    /// The number of seconds since the Unix epoch.
+    pub seconds: i64,
+    /// The number of nanoseconds since the last whole second.
+    pub nanoseconds: u32,
+    /// The offset from UTC in seconds, which is zero unless given explicitly.
+    pub offset: i32,
+}
+
+impl Timestamp {
+    /// Decode a value.
+    ///
+    /// Strings are expected to be in one of the formats `YYYY-MM-DD`, `YYYY-MM-DD HH:MM`,
+    /// `YYYY-MM-DD HH:MM:SS`, `YYYY-MM-DD HH:MM:SS.SSS`, `HH:MM`, `HH:MM:SS`, or `HH:MM:SS.SSS`
+    /// with an optional `T` separator and an optional time zone, or to contain a Julian day
+    /// number. Floats are Julian day numbers, and integers are Unix timestamps.
+    pub fn parse(value: &Value) -> Result<Self> {
+        match value {
+            Value::String(ref value) => parse(value),
+            &Value::Float(value) => julian(value),
+            &Value::Integer(value) => Ok(Timestamp {
+                seconds: value,
+                nanoseconds: 0,
+                offset: 0,
+            }),
+            _ => raise!("failed to convert"),
+        }
+    }
+}
+
+// Implement binding, reading, and conversion for a date or time type given how to format it and
+// how to obtain it from a timestamp.
+macro_rules! implement(
+    ($type:ty, |$value:ident| $format:expr, |$timestamp:ident| $parse:expr) => {
+        impl crate::statement::BindableWithIndex for $type {
+            #[inline]
+            fn bind<T: crate::statement::ParameterIndex>(
+                self,
+                statement: &mut crate::statement::Statement,
+                index: T,
+            ) -> crate::error::Result<()> {
+                let $value = self;
+                $format?.bind(statement, index)
+            }
+        }
+
+        impl crate::statement::ReadableWithIndex for $type {
+            #[inline]
+            fn read<T: crate::statement::ColumnIndex>(
+                statement: &crate::statement::Statement,
+                index: T,
+            ) -> crate::error::Result<Self> {
+                <$type>::try_from(&crate::value::Value::read(statement, index)?)
+            }
+        }
+
+        impl TryFrom<&crate::value::Value> for $type {
+            type Error = crate::error::Error;
+
+            fn try_from(value: &crate::value::Value) -> crate::error::Result<Self> {
+                let $timestamp = crate::datetime::Timestamp::parse(value)?;
+                match $parse {
+                    Some(value) => Ok(value),
+                    _ => raise!("the date or time is out of range ({value:?})"),
+                }
+            }
+        }
+
+        impl TryFrom<&crate::value::Value> for Option<$type> {
+            type Error = crate::error::Error;
+
+            #[inline]
+            fn try_from(value: &crate::value::Value) -> crate::error::Result<Self> {
+                if let crate::value::Value::Null = value {
+                    return Ok(None);
+                }
+                <$type>::try_from(value).map(Some)
+            }
+        }
+    };
+);
+
+pub(crate) use implement;
+
+/// Format a date as `YYYY-MM-DD`.
+pub fn format_date(year: i32, month: u32, day: u32) -> Result<String> {
+    if !(0..=9999).contains(&year) {
+        raise!("the year is out of range ({year})");
+    }
+    Ok(format!("{year:04}-{month:02}-{day:02}"))
+}
+
+/// Format a time as `HH:MM:SS` followed by as many fractional digits as needed.
+pub fn format_time(hour: u32, minute: u32, second: u32, nanosecond: u32) -> String {
+    // Leap seconds are represented by some libraries via an overflow of nanoseconds.
+    let nanosecond = nanosecond.min(NANOSECONDS_PER_SECOND as u32 - 1);
+    if nanosecond == 0 {
+        return format!("{hour:02}:{minute:02}:{second:02}");
+    }
+    let fraction = format!("{nanosecond:09}");
+    let fraction = fraction.trim_end_matches('0');
+    format!("{hour:02}:{minute:02}:{second:02}.{fraction}")
+}
+
+/// Format an offset from UTC as `+HH:MM` or `-HH:MM`.
+pub fn format_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let minutes = seconds.unsigned_abs() / 60;
+    format!("{sign}{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+struct Parser<'l> {
+    bytes: &'l [u8],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn consume(&mut self, byte: u8) -> bool {
+        if self.bytes.get(self.position) == Some(&byte) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn digits(&mut self, count: usize) -> Option<u32> {
+        let bytes = self.bytes.get(self.position..self.position + count)?;
+        if !bytes.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        self.position += count;
+        Some(
+            bytes
+                .iter()
+                .fold(0, |value, byte| 10 * value + (byte - b'0') as u32),
+        )
+    }
+
+    fn fraction(&mut self) -> u32 {
+        let mut nanoseconds = 0;
+        let mut scale = NANOSECONDS_PER_SECOND as u32;
+        while let Some(&byte) = self.bytes.get(self.position) {
+            if !byte.is_ascii_digit() {
+                break;
+            }
+            scale /= 10;
+            nanoseconds += (byte - b'0') as u32 * scale;
+            self.position += 1;
+        }
+        nanoseconds
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.position < self.bytes.len() && self.bytes[self.position].is_ascii_whitespace() {
+            self.position += 1;
+        }
+    }
+
+    fn date(&mut self) -> Option<(i32, u32, u32)> {
+        let position = self.position;
+        let date = (|| {
+            let year = self.digits(4)?;
+            self.consume(b'-').then_some(())?;
+            let month = self.digits(2)?;
+            self.consume(b'-').then_some(())?;
+            let day = self.digits(2)?;
+            Some((year as i32, month, day))
+        })();
+        if date.is_none() {
+            self.position = position;
+        }
+        date
+    }
+
+    fn time(&mut self) -> Option<(u32, u32, u32, u32)> {
+        let hour = self.digits(2)?;
+        self.consume(b':').then_some(())?;
+        let minute = self.digits(2)?;
+        let (second, nanosecond) = if self.consume(b':') {
+            let second = self.digits(2)?;
+            // The dot is part of the seconds only if followed by a digit as in SQLite.
+            let digit = self
+                .bytes
+                .get(self.position + 1)
+                .is_some_and(u8::is_ascii_digit);
+            let nanosecond = if digit && self.consume(b'.') {
+                self.fraction()
+            } else {
+                0
+            };
+            (second, nanosecond)
+        } else {
+            (0, 0)
+        };
+        Some((hour, minute, second, nanosecond))
+    }
+
+    fn offset(&mut self) -> Option<i32> {
+        if self.consume(b'Z') || self.consume(b'z') {
+            return Some(0);
+        }
+        let sign = if self.consume(b'+') {
+            1
+        } else if self.consume(b'-') {
+            -1
+        } else {
+            return Some(0);
+        };
+        let hour = self.digits(2)?;
+        self.consume(b':');
+        let minute = self.digits(2)?;
+        if hour > 14 || minute > 59 {
+            return None;
+        }
+        Some(sign * (hour * 3600 + minute * 60) as i32)
+    }
+}
+
+fn parse(value: &str) -> Result<Timestamp> {
+    let value = value.trim();
+    if let Ok(value) = value.parse::<f64>() {
+        return julian(value);
+    }
+    let mut parser = Parser {
+        bytes: value.as_bytes(),
+        position: 0,
+    };
+    let date = parser.date();
+    if date.is_some() {
+        parser.skip_whitespace();
+        parser.consume(b'T');
+    }
+    let time = if date.is_none() || parser.position < parser.bytes.len() {
+        match parser.time() {
+            Some(time) => time,
+            _ => raise!("failed to parse a date or time ({value})"),
+        }
+    } else {
+        (0, 0, 0, 0)
+    };
+    parser.skip_whitespace();
+    let offset = parser.offset();
+    parser.skip_whitespace();
+    let offset = match offset {
+        Some(offset) if parser.position == parser.bytes.len() => offset,
+        _ => raise!("failed to parse a date or time ({value})"),
+    };
+    // A time without a date refers to 2000-01-01, the same as in SQLite.
+    let (year, month, day) = date.unwrap_or((2000, 1, 1));
+    let (hour, minute, second, nanosecond) = time;
+    if !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        raise!("the date or time is out of range ({value})");
+    }
+    let seconds = days_from_civil(year, month, day) * SECONDS_PER_DAY
+        + (hour * 3600 + minute * 60 + second) as i64
+        - offset as i64;
+    Ok(Timestamp {
+        seconds,
+        nanoseconds: nanosecond,
+        offset,
+    })
+}
+
+fn julian(value: f64) -> Result<Timestamp> {
+    if !(0.0..=MAX_JULIAN_DAY).contains(&value) {
+        raise!("the Julian day is out of range ({value})");
+    }
+    // SQLite operates with millisecond precision.
+    let milliseconds = (value * 86_400_000.0).round() as i64 - UNIX_EPOCH_IN_JULIAN_MILLISECONDS;
+    Ok(Timestamp {
+        seconds: milliseconds.div_euclid(1000),
+        nanoseconds: milliseconds.rem_euclid(1000) as u32 * 1_000_000,
+        offset: 0,
+    })
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// The algorithm is described in http://howardhinnant.github.io/date_algorithms.html.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year } as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month as i64 + 9) % 12) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
@@ -1,12 +1,26 @@
+use core::ffi::c_int;
 use std::{error, fmt};
 
 /// An error.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Error {
     /// The error code.
     pub code: Option<isize>,
     /// The error message.
     pub message: Option<String>,
+    /// The error kind.
+    pub kind: ErrorKind,
+}
+
+/// The kind of an error.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// An error without a more specific kind.
+    #[default]
+    Other,
+    /// A value that does not fit into the target type.
+    OutOfRange,
 }
 
 /// A result.
@@ -19,6 +33,7 @@ macro_rules! error(
             _ => return Err(crate::error::Error {
                 code: Some($code as isize),
                 message: None,
+                kind: crate::error::ErrorKind::Other,
             }),
         }
     );
@@ -37,6 +52,7 @@ macro_rules! ok(
             code => return Err(crate::error::Error {
                 code: Some(code as isize),
                 message: None,
+                kind: crate::error::ErrorKind::Other,
             }),
         }
     );
@@ -44,10 +60,7 @@ macro_rules! ok(
 
 macro_rules! raise(
     ($message:expr $(, $($token:tt)* )?) => (
-        return Err(crate::error::Error {
-            code: None,
-            message: Some(format!($message $(, $($token)* )*)),
-        })
+        return Err(crate::error::failure(format!($message $(, $($token)* )*)))
     );
 );
 
@@ -84,6 +97,31 @@ pub fn last(raw: *mut ffi::sqlite3) -> Option<Error> {
         Some(Error {
             code: Some(code as isize),
             message: Some(c_str_to_string!(message)),
+            kind: ErrorKind::Other,
         })
     }
 }
+
+pub(crate) fn error(code: c_int) -> Error {
+    Error {
+        code: Some(code as isize),
+        message: None,
+        kind: ErrorKind::Other,
+    }
+}
+
+pub(crate) fn failure(message: String) -> Error {
+    Error {
+        code: None,
+        message: Some(message),
+        kind: ErrorKind::Other,
+    }
+}
+
+pub(crate) fn out_of_range<T: fmt::Display>(value: T) -> Error {
+    Error {
+        code: None,
+        message: Some(format!("the value is out of range ({value})")),
+        kind: ErrorKind::OutOfRange,
+    }
+}
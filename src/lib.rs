@@ -124,22 +124,84 @@ macro_rules! str_to_cstr(
     );
 );
 
+// https://sqlite.org/c3ref/c_static.html
+macro_rules! transient(
+    () => (
+        std::mem::transmute::<
+            *const std::ffi::c_void,
+            std::option::Option<unsafe extern "C" fn(*mut std::ffi::c_void)>
+        >(!0 as *const core::ffi::c_void)
+    );
+);
+
 #[macro_use]
 mod error;
 mod value;
 
+mod array;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod bulk;
+#[cfg(feature = "chrono")]
+mod chrono;
 mod connection;
+#[cfg(feature = "csv")]
+mod csv;
 mod cursor;
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod datetime;
+mod memory;
+mod plan;
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "serde_json")]
+mod serde_json;
+#[cfg(feature = "serialize")]
+mod serialize;
+#[cfg(feature = "session")]
+mod session;
 mod statement;
+#[cfg(feature = "testing")]
+mod testing;
+#[cfg(feature = "time")]
+mod time;
+#[cfg(feature = "uuid")]
+mod uuid;
+mod vfs;
+mod vtab;
 
-pub use error::{Error, Result};
-pub use value::{Type, Value};
+pub use error::{Error, ErrorKind, Result};
+pub use value::{Type, Value, ValueRef};
+pub use vfs::{AccessCheck, FileKind, FileOptions, LockLevel, ShmRegion, Vfs, VfsFile};
+pub use vtab::{
+    ConstraintOperator, IndexConstraint, IndexInfo, IndexOrderBy, Module, Update, VTab, VTabCursor,
+};
 
-pub use connection::{Connection, ConnectionThreadSafe, OpenFlags};
-pub use cursor::{Cursor, CursorWithOwnership, Row, RowIndex};
+#[cfg(feature = "serde")]
+pub use self::serde::Serialized;
+pub use bulk::{BulkInserter, DEFAULT_BATCH_SIZE};
+pub use connection::{Connection, ConnectionThreadSafe, DbStatus, OpenFlags};
+#[cfg(feature = "csv")]
+pub use csv::{
+    BlobEncoding, CsvExportOptions, CsvImport, CsvImportOptions, DEFAULT_INFERENCE_SIZE,
+};
+pub use cursor::{Cursor, CursorWithOwnership, Row, RowIndex, RowRef, RowsRef};
+pub use memory::{MemoryFile, MemoryVfs};
+pub use plan::{PlanNode, QueryPlan};
+#[cfg(feature = "serialize")]
+pub use serialize::{DeserializeMode, SerializedDb};
+#[cfg(feature = "session")]
+pub use session::{Change, Changeset, ConflictAction, ConflictType, Operation, Session};
+#[cfg(feature = "derive")]
+pub use sqlite_derive::{Bindable, FromRow};
 pub use statement::{
-    Bindable, BindableWithIndex, ColumnIndex, ParameterIndex, ReadableWithIndex, State, Statement,
+    Bindable, BindableWithIndex, ColumnIndex, FromRow, FromSql, ParameterIndex, Parameters,
+    ReadableWithIndex, State, Statement, StatementStatus, ToSql, ToSqlOutput,
 };
+#[cfg(feature = "testing")]
+pub use testing::{FaultFile, FaultInjector, FaultVfs, SyncFault, WriteFault};
 
 /// Open a read-write connection to a new or existing database.
 #[inline]
@@ -147,6 +209,19 @@ pub fn open<T: AsRef<std::path::Path>>(path: T) -> Result<Connection> {
     Connection::open(path)
 }
 
+/// Register a virtual file system.
+///
+/// The name is used to select the virtual file system when opening connections via
+/// `Connection::open_with_flags_and_vfs`, and it cannot be the name of an already registered
+/// virtual file system. Registered virtual file systems live until the process exits. If
+/// `make_default` is true, the virtual file system is used for all connections opened without
+/// specifying one. Registrations via this function are serialized, but they are not to race with
+/// registrations made directly via SQLite.
+#[inline]
+pub fn register_vfs<T: Vfs>(name: &str, vfs: T, make_default: bool) -> Result<()> {
+    vfs::register(name, vfs, make_default)
+}
+
 /// Return the version number of SQLite.
 ///
 /// For instance, the version `3.8.11.1` corresponds to the integer `3008011`.
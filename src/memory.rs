@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::error::{error, Result};
+use crate::vfs::{lock, AccessCheck, FileOptions, LockLevel, ShmRegion, Vfs, VfsFile};
+
+/// A virtual file system keeping files in memory.
+///
+/// Connections opened with the same path via the same registered instance share the same
+/// database, including in the write-ahead logging mode. Clones of an instance share the same
+/// files, which makes it possible to take snapshots after registration.
+///
+/// # Examples
+///
+/// ```
+/// use sqlite::{Connection, MemoryVfs, OpenFlags};
+///
+/// let vfs = MemoryVfs::new();
+/// sqlite::register_vfs("memory", vfs.clone(), false)?;
+///
+/// let flags = OpenFlags::new().with_create().with_read_write();
+/// let writer = Connection::open_with_flags_and_vfs("database", flags, "memory")?;
+/// let reader = Connection::open_with_flags_and_vfs("database", flags, "memory")?;
+/// writer.execute("PRAGMA journal_mode = WAL")?;
+/// writer.execute("CREATE TABLE users (name TEXT)")?;
+/// writer.execute("INSERT INTO users VALUES ('Alice')")?;
+/// let (count,) = reader.query_one::<(i64,), _, _>("SELECT count(*) FROM users", ())?;
+/// assert_eq!(count, 1);
+///
+/// writer.execute("PRAGMA wal_checkpoint(TRUNCATE)")?;
+/// let data = vfs.snapshot("database")?;
+/// vfs.restore("copy", &data)?;
+/// let copy = Connection::open_with_flags_and_vfs("copy", flags, "memory")?;
+/// let (count,) = copy.query_one::<(i64,), _, _>("SELECT count(*) FROM users", ())?;
+/// assert_eq!(count, 1);
+/// # Ok::<(), sqlite::Error>(())
+/// ```
+#[derive(Clone, Default)]
+pub struct MemoryVfs {
+    files: Arc<Mutex<HashMap<String, Arc<Mutex<Shared>>>>>,
+}
+
+/// A file of a `MemoryVfs`.
+pub struct MemoryFile {
+    shared: Arc<Mutex<Shared>>,
+    level: LockLevel,
+    reserved: bool,
+    pending: bool,
+    shm_shared: u8,
+    shm_exclusive: u8,
+    shm_mapped: bool,
+    delete: Option<(MemoryVfs, String)>,
+}
+
+#[derive(Default)]
+struct Shared {
+    data: Vec<u8>,
+    shared: usize,
+    reserved: bool,
+    pending: bool,
+    exclusive: bool,
+    shm: Vec<ShmRegion>,
+    shm_mappers: usize,
+    shm_readers: [usize; ffi::SQLITE_SHM_NLOCK as usize],
+    shm_exclusive: u8,
+}
+
+impl MemoryVfs {
+    /// Create a virtual file system.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return a copy of the content of a file.
+    ///
+    /// In the write-ahead logging mode, the log is to be checkpointed beforehand in order for
+    /// the database file to contain all committed transactions.
+    pub fn snapshot(&self, path: &str) -> Result<Vec<u8>> {
+        match lock(&self.files).get(path) {
+            Some(file) => Ok(lock(file).data.clone()),
+            _ => raise!("the file does not exist"),
+        }
+    }
+
+    /// Replace the content of a file, deleting its rollback journal and write-ahead log if any.
+    ///
+    /// The file is not supposed to be opened by any connection.
+    pub fn restore(&self, path: &str, data: &[u8]) -> Result<()> {
+        let mut files = lock(&self.files);
+        files.remove(&format!("{path}-journal"));
+        files.remove(&format!("{path}-wal"));
+        let shared = Shared {
+            data: data.to_vec(),
+            ..Default::default()
+        };
+        files.insert(path.into(), Arc::new(Mutex::new(shared)));
+        Ok(())
+    }
+}
+
+impl Vfs for MemoryVfs {
+    type File = MemoryFile;
+
+    fn open(&self, path: Option<&str>, options: FileOptions) -> Result<MemoryFile> {
+        let mut files = lock(&self.files);
+        let shared = match path.map(|path| (path, files.get(path))) {
+            Some((_, Some(_))) if options.is_exclusive() => {
+                return Err(error(ffi::SQLITE_CANTOPEN))
+            }
+            Some((_, Some(shared))) => shared.clone(),
+            Some(_) if !options.is_create() => return Err(error(ffi::SQLITE_CANTOPEN)),
+            Some((path, _)) => files.entry(path.into()).or_default().clone(),
+            // Temporary files are not accessible by name.
+            _ => Default::default(),
+        };
+        Ok(MemoryFile {
+            shared,
+            level: LockLevel::None,
+            reserved: false,
+            pending: false,
+            shm_shared: 0,
+            shm_exclusive: 0,
+            shm_mapped: false,
+            delete: path
+                .filter(|_| options.is_delete_on_close())
+                .map(|path| (self.clone(), path.into())),
+        })
+    }
+
+    fn delete(&self, path: &str, _: bool) -> Result<()> {
+        lock(&self.files).remove(path);
+        Ok(())
+    }
+
+    fn access(&self, path: &str, _: AccessCheck) -> Result<bool> {
+        Ok(lock(&self.files).contains_key(path))
+    }
+}
+
+impl VfsFile for MemoryFile {
+    const SHARED_MEMORY: bool = true;
+
+    fn read(&mut self, buffer: &mut [u8], offset: u64) -> Result<usize> {
+        let shared = lock(&self.shared);
+        let data = shared.data.get(offset as usize..).unwrap_or_default();
+        let count = data.len().min(buffer.len());
+        buffer[..count].copy_from_slice(&data[..count]);
+        Ok(count)
+    }
+
+    fn write(&mut self, data: &[u8], offset: u64) -> Result<()> {
+        let mut shared = lock(&self.shared);
+        let end = offset as usize + data.len();
+        if shared.data.len() < end {
+            shared.data.resize(end, 0);
+        }
+        shared.data[offset as usize..end].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn truncate(&mut self, size: u64) -> Result<()> {
+        lock(&self.shared).data.resize(size as usize, 0);
+        Ok(())
+    }
+
+    #[inline]
+    fn sync(&mut self, _: bool) -> Result<()> {
+        Ok(())
+    }
+
+    fn file_size(&self) -> Result<u64> {
+        Ok(lock(&self.shared).data.len() as u64)
+    }
+
+    fn lock(&mut self, level: LockLevel) -> Result<()> {
+        if self.level >= level {
+            return Ok(());
+        }
+        let mut shared = lock(&self.shared);
+        match level {
+            LockLevel::None => {}
+            LockLevel::Shared => {
+                if shared.pending || shared.exclusive {
+                    return Err(error(ffi::SQLITE_BUSY));
+                }
+                shared.shared += 1;
+            }
+            LockLevel::Reserved => {
+                if shared.reserved {
+                    return Err(error(ffi::SQLITE_BUSY));
+                }
+                shared.reserved = true;
+                self.reserved = true;
+            }
+            LockLevel::Pending | LockLevel::Exclusive => {
+                if !self.pending {
+                    if shared.pending {
+                        return Err(error(ffi::SQLITE_BUSY));
+                    }
+                    shared.pending = true;
+                    self.pending = true;
+                    self.level = LockLevel::Pending;
+                }
+                // The shared lock of the file itself is the only one allowed.
+                if level == LockLevel::Exclusive {
+                    if shared.shared > 1 {
+                        return Err(error(ffi::SQLITE_BUSY));
+                    }
+                    shared.exclusive = true;
+                }
+            }
+        }
+        self.level = level;
+        Ok(())
+    }
+
+    fn unlock(&mut self, level: LockLevel) -> Result<()> {
+        if self.level <= level {
+            return Ok(());
+        }
+        let mut shared = lock(&self.shared);
+        if self.level == LockLevel::Exclusive {
+            shared.exclusive = false;
+        }
+        if std::mem::take(&mut self.pending) {
+            shared.pending = false;
+        }
+        if std::mem::take(&mut self.reserved) {
+            shared.reserved = false;
+        }
+        if level == LockLevel::None {
+            shared.shared -= 1;
+        }
+        self.level = level;
+        Ok(())
+    }
+
+    fn check_reserved_lock(&self) -> Result<bool> {
+        let shared = lock(&self.shared);
+        Ok(shared.reserved || shared.pending || shared.exclusive)
+    }
+
+    fn shm_map(&mut self, region: usize, size: usize, extend: bool) -> Result<Option<ShmRegion>> {
+        let mut shared = lock(&self.shared);
+        if region >= shared.shm.len() {
+            if !extend {
+                return Ok(None);
+            }
+            while region >= shared.shm.len() {
+                shared.shm.push(ShmRegion::new(size));
+            }
+        }
+        if !self.shm_mapped {
+            shared.shm_mappers += 1;
+            self.shm_mapped = true;
+        }
+        Ok(Some(shared.shm[region].clone()))
+    }
+
+    fn shm_lock(&mut self, offset: usize, count: usize, exclusive: bool) -> Result<()> {
+        let mut shared = lock(&self.shared);
+        let mask = slots(offset, count);
+        for slot in offset..offset + count {
+            let bit = 1 << slot;
+            if shared.shm_exclusive & bit != 0 && self.shm_exclusive & bit == 0 {
+                return Err(error(ffi::SQLITE_BUSY));
+            }
+            let readers = usize::from(self.shm_shared & bit != 0);
+            if exclusive && shared.shm_readers[slot] > readers {
+                return Err(error(ffi::SQLITE_BUSY));
+            }
+        }
+        if exclusive {
+            shared.shm_exclusive |= mask;
+            self.shm_exclusive |= mask;
+        } else {
+            for slot in offset..offset + count {
+                if self.shm_shared & (1 << slot) == 0 {
+                    shared.shm_readers[slot] += 1;
+                }
+            }
+            self.shm_shared |= mask;
+        }
+        Ok(())
+    }
+
+    fn shm_unlock(&mut self, offset: usize, count: usize, exclusive: bool) -> Result<()> {
+        let shared = self.shared.clone();
+        let mut shared = lock(&shared);
+        self.release(&mut shared, slots(offset, count), exclusive);
+        Ok(())
+    }
+
+    fn shm_unmap(&mut self, _: bool) -> Result<()> {
+        let shared = self.shared.clone();
+        let mut shared = lock(&shared);
+        self.release(&mut shared, !0, false);
+        self.release(&mut shared, !0, true);
+        if std::mem::take(&mut self.shm_mapped) {
+            shared.shm_mappers -= 1;
+            // The shared memory is rebuilt by the next connection.
+            if shared.shm_mappers == 0 {
+                shared.shm.clear();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MemoryFile {
+    fn release(&mut self, shared: &mut Shared, mask: u8, exclusive: bool) {
+        if exclusive {
+            shared.shm_exclusive &= !(mask & self.shm_exclusive);
+            self.shm_exclusive &= !mask;
+        } else {
+            for slot in 0..ffi::SQLITE_SHM_NLOCK as usize {
+                if mask & self.shm_shared & (1 << slot) != 0 {
+                    shared.shm_readers[slot] -= 1;
+                }
+            }
+            self.shm_shared &= !mask;
+        }
+    }
+}
+
+impl Drop for MemoryFile {
+    fn drop(&mut self) {
+        let _ = self.shm_unmap(false);
+        let _ = self.unlock(LockLevel::None);
+        if let Some((vfs, path)) = self.delete.take() {
+            let _ = vfs.delete(&path, false);
+        }
+    }
+}
+
+fn slots(offset: usize, count: usize) -> u8 {
+    (((1u16 << count) - 1) << offset) as u8
+}
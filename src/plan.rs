@@ -0,0 +1,121 @@
+use std::fmt;
+
+use crate::connection::Connection;
+use crate::error::Result;
+use crate::statement::State;
+
+/// A query plan.
+///
+/// The plan is produced by `EXPLAIN QUERY PLAN`; see the [documentation][1] of SQLite for further
+/// details.
+///
+/// [1]: https://www.sqlite.org/eqp.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryPlan {
+    nodes: Vec<PlanNode>,
+}
+
+/// A node of a query plan.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlanNode {
+    /// The identifier.
+    pub id: i64,
+    /// The identifier of the parent node, which is zero for top-level nodes.
+    pub parent: i64,
+    /// The description.
+    pub detail: String,
+}
+
+impl QueryPlan {
+    /// Return all nodes in the order reported by SQLite.
+    #[inline]
+    pub fn nodes(&self) -> &[PlanNode] {
+        &self.nodes
+    }
+
+    /// Return the top-level nodes.
+    #[inline]
+    pub fn roots(&self) -> impl Iterator<Item = &PlanNode> {
+        self.children(0)
+    }
+
+    /// Return the nodes whose parent has a specific identifier.
+    pub fn children(&self, id: i64) -> impl Iterator<Item = &PlanNode> {
+        self.nodes.iter().filter(move |node| node.parent == id)
+    }
+
+    /// Check if any table is scanned entirely.
+    #[inline]
+    pub fn uses_full_scan(&self) -> bool {
+        self.nodes.iter().any(PlanNode::is_full_scan)
+    }
+
+    /// Check if a specific index is used.
+    #[inline]
+    pub fn uses_index(&self, name: &str) -> bool {
+        self.nodes.iter().any(|node| node.uses_index(name))
+    }
+
+    /// Check if a temporary B-tree is used for sorting or grouping.
+    #[inline]
+    pub fn uses_temp_btree(&self) -> bool {
+        self.nodes.iter().any(PlanNode::uses_temp_btree)
+    }
+
+    fn format(&self, formatter: &mut fmt::Formatter, id: i64, prefix: &str) -> fmt::Result {
+        let mut children = self.children(id).peekable();
+        while let Some(node) = children.next() {
+            let last = children.peek().is_none();
+            let branch = if last { "`--" } else { "|--" };
+            writeln!(formatter, "{prefix}{branch}{}", node.detail)?;
+            let prefix = format!("{prefix}{}", if last { "   " } else { "|  " });
+            self.format(formatter, node.id, &prefix)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for QueryPlan {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(formatter, "QUERY PLAN")?;
+        self.format(formatter, 0, "")
+    }
+}
+
+impl PlanNode {
+    /// Check if the node scans a table entirely.
+    pub fn is_full_scan(&self) -> bool {
+        self.detail.starts_with("SCAN ") && self.detail != "SCAN CONSTANT ROW"
+    }
+
+    /// Check if the node uses a specific index.
+    pub fn uses_index(&self, name: &str) -> bool {
+        let mut words = self.detail.split(' ').skip_while(|word| *word != "USING");
+        while let Some(word) = words.next() {
+            if word == "INDEX" {
+                return words.next() == Some(name);
+            }
+        }
+        false
+    }
+
+    /// Check if the node uses a temporary B-tree.
+    #[inline]
+    pub fn uses_temp_btree(&self) -> bool {
+        self.detail.contains("TEMP B-TREE")
+    }
+}
+
+pub fn new<T: AsRef<str>>(connection: &Connection, statement: T) -> Result<QueryPlan> {
+    let query = format!("EXPLAIN QUERY PLAN {}", statement.as_ref());
+    let mut statement = connection.prepare(query)?;
+    let mut nodes = Vec::new();
+    while let State::Row = statement.next()? {
+        nodes.push(PlanNode {
+            id: statement.read(0)?,
+            parent: statement.read(1)?,
+            detail: statement.read(3)?,
+        });
+    }
+    Ok(QueryPlan { nodes })
+}
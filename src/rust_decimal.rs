@@ -0,0 +1,63 @@
+use std::str::FromStr;
+
+use ::rust_decimal::Decimal;
+
+use crate::error::{Error, Result};
+use crate::statement::{
+    BindableWithIndex, ColumnIndex, ParameterIndex, ReadableWithIndex, Statement,
+};
+use crate::value::Value;
+
+impl BindableWithIndex for Decimal {
+    #[inline]
+    fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+        self.to_string().bind(statement, index)
+    }
+}
+
+impl From<Decimal> for Value {
+    #[inline]
+    fn from(value: Decimal) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl ReadableWithIndex for Decimal {
+    #[inline]
+    fn read<T: ColumnIndex>(statement: &Statement, index: T) -> Result<Self> {
+        Decimal::try_from(&Value::read(statement, index)?)
+    }
+}
+
+impl TryFrom<&Value> for Decimal {
+    type Error = Error;
+
+    /// Convert a string, preserving the precision, or an integer or a float, which can be the
+    /// result of numeric type affinity or arithmetic.
+    fn try_from(value: &Value) -> Result<Self> {
+        match value {
+            Value::String(ref value) => match Decimal::from_str(value) {
+                Ok(value) => Ok(value),
+                Err(error) => raise!("failed to parse a decimal ({error})"),
+            },
+            &Value::Integer(value) => Ok(Decimal::from(value)),
+            &Value::Float(value) => match Decimal::try_from(value) {
+                Ok(value) => Ok(value),
+                _ => Err(crate::error::out_of_range(value)),
+            },
+            _ => raise!("failed to convert"),
+        }
+    }
+}
+
+impl TryFrom<&Value> for Option<Decimal> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &Value) -> Result<Self> {
+        if let Value::Null = value {
+            return Ok(None);
+        }
+        Decimal::try_from(value).map(Some)
+    }
+}
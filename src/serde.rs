@@ -0,0 +1,839 @@
+use std::cell::OnceCell;
+use std::fmt;
+
+use ::serde::de::value::BorrowedStrDeserializer;
+use ::serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use ::serde::ser::{self, Impossible};
+use ::serde::{forward_to_deserialize_any, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::cursor::Row;
+use crate::error::{Error, ErrorKind, Result};
+use crate::statement::{Bindable, BindableWithIndex, Statement};
+use crate::value::Value;
+
+/// A value to be bound to a prepared statement via [serde][1].
+///
+/// Structs and maps are bound to named parameters, and sequences and tuples are bound to
+/// positional parameters. Names without a prefix are prefixed with a colon.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Serialize;
+/// use sqlite::{Serialized, State};
+///
+/// #[derive(Serialize)]
+/// struct User<'l> {
+///     id: i64,
+///     name: &'l str,
+/// }
+///
+/// # let connection = sqlite::open(":memory:").unwrap();
+/// # connection.execute("CREATE TABLE users (id INTEGER, name STRING)");
+/// let query = "INSERT INTO users VALUES (:id, :name)";
+/// let mut statement = connection.prepare(query)?;
+/// statement.bind(Serialized(&User { id: 1, name: "Alice" }))?;
+/// assert_eq!(statement.next()?, State::Done);
+/// # Ok::<(), sqlite::Error>(())
+/// ```
+///
+/// [1]: https://serde.rs
+#[derive(Clone, Copy, Debug)]
+pub struct Serialized<T>(pub T);
+
+impl Row {
+    /// Deserialize the row via [serde][1].
+    ///
+    /// Structs and maps are read by column name, and sequences and tuples are read by position.
+    /// A row with a single column can also be deserialized as the value of that column.
+    ///
+    /// [1]: https://serde.rs
+    #[inline]
+    pub fn deserialize<'l, T: Deserialize<'l>>(&'l self) -> Result<T> {
+        for index in 0..self.values.len() {
+            self.value(index)?;
+        }
+        T::deserialize(RowDeserializer {
+            names: &self.column_names,
+            values: &self.values,
+        })
+    }
+}
+
+impl Statement<'_> {
+    /// Deserialize the current row via [serde][1].
+    ///
+    /// See `Row::deserialize` for further details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use sqlite::State;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String,
+    /// }
+    ///
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name STRING)");
+    /// # connection.execute("INSERT INTO users VALUES (1, 'Alice')");
+    /// let mut statement = connection.prepare("SELECT * FROM users")?;
+    /// assert_eq!(statement.next()?, State::Row);
+    /// let user = statement.deserialize::<User>()?;
+    /// assert_eq!(user.name, "Alice");
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    ///
+    /// [1]: https://serde.rs
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T> {
+        let values = (0..self.column_count())
+            .map(|index| self.read::<Value, _>(index).map(OnceCell::from))
+            .collect::<Result<Vec<_>>>()?;
+        T::deserialize(RowDeserializer {
+            names: &self.column_names,
+            values: &values,
+        })
+    }
+}
+
+impl<T: Serialize> Bindable for Serialized<T> {
+    #[inline]
+    fn bind(self, statement: &mut Statement) -> Result<()> {
+        self.0.serialize(ParameterSerializer { statement })
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Error {
+            code: None,
+            message: Some(message.to_string()),
+            kind: ErrorKind::Other,
+        }
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Error {
+            code: None,
+            message: Some(message.to_string()),
+            kind: ErrorKind::Other,
+        }
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Value::Binary(ref value) => serializer.serialize_bytes(value),
+            Value::Float(value) => serializer.serialize_f64(*value),
+            Value::Integer(value) => serializer.serialize_i64(*value),
+            Value::String(ref value) => serializer.serialize_str(value),
+            Value::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'l> Deserialize<'l> for Value {
+    fn deserialize<D: Deserializer<'l>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'l> Visitor<'l> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a value")
+    }
+
+    #[inline]
+    fn visit_bool<E>(self, value: bool) -> std::result::Result<Value, E> {
+        Ok(Value::Integer(value as i64))
+    }
+
+    #[inline]
+    fn visit_i64<E>(self, value: i64) -> std::result::Result<Value, E> {
+        Ok(Value::Integer(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> std::result::Result<Value, E> {
+        match i64::try_from(value) {
+            Ok(value) => Ok(Value::Integer(value)),
+            _ => Err(E::custom(format!("the integer is out of range ({value})"))),
+        }
+    }
+
+    #[inline]
+    fn visit_f64<E>(self, value: f64) -> std::result::Result<Value, E> {
+        Ok(Value::Float(value))
+    }
+
+    #[inline]
+    fn visit_str<E>(self, value: &str) -> std::result::Result<Value, E> {
+        Ok(Value::String(value.into()))
+    }
+
+    #[inline]
+    fn visit_string<E>(self, value: String) -> std::result::Result<Value, E> {
+        Ok(Value::String(value))
+    }
+
+    #[inline]
+    fn visit_bytes<E>(self, value: &[u8]) -> std::result::Result<Value, E> {
+        Ok(Value::Binary(value.into()))
+    }
+
+    #[inline]
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> std::result::Result<Value, E> {
+        Ok(Value::Binary(value))
+    }
+
+    #[inline]
+    fn visit_none<E>(self) -> std::result::Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn visit_some<D: Deserializer<'l>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    #[inline]
+    fn visit_unit<E>(self) -> std::result::Result<Value, E> {
+        Ok(Value::Null)
+    }
+}
+
+struct RowDeserializer<'l> {
+    names: &'l [String],
+    values: &'l [OnceCell<Value>],
+}
+
+static NULL: Value = Value::Null;
+
+#[inline]
+fn get(value: &OnceCell<Value>) -> &Value {
+    value.get().unwrap_or(&NULL)
+}
+
+macro_rules! forward_to_value(
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'l>>(self, visitor: V) -> Result<V::Value> {
+                match self.values {
+                    [value] => ValueDeserializer(get(value)).$method(visitor),
+                    _ => raise!("expected a single column but found {}", self.values.len()),
+                }
+            }
+        )*
+    };
+);
+
+impl<'l> Deserializer<'l> for RowDeserializer<'l> {
+    type Error = Error;
+
+    #[inline]
+    fn deserialize_any<V: Visitor<'l>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'l>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_map(RowMapAccess {
+            names: self.names.iter(),
+            values: self.values.iter(),
+            value: None,
+        })
+    }
+
+    #[inline]
+    fn deserialize_struct<V: Visitor<'l>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'l>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(ValueSeqAccess(self.values.iter()))
+    }
+
+    #[inline]
+    fn deserialize_tuple<V: Visitor<'l>>(self, _: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V: Visitor<'l>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V: Visitor<'l>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    #[inline]
+    fn deserialize_unit<V: Visitor<'l>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    #[inline]
+    fn deserialize_unit_struct<V: Visitor<'l>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    #[inline]
+    fn deserialize_ignored_any<V: Visitor<'l>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_enum<V: Visitor<'l>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.values {
+            [value] => ValueDeserializer(get(value)).deserialize_enum(name, variants, visitor),
+            _ => raise!("expected a single column but found {}", self.values.len()),
+        }
+    }
+
+    forward_to_value! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_option
+        deserialize_identifier
+    }
+}
+
+struct RowMapAccess<'l> {
+    names: std::slice::Iter<'l, String>,
+    values: std::slice::Iter<'l, OnceCell<Value>>,
+    value: Option<&'l Value>,
+}
+
+impl<'l> MapAccess<'l> for RowMapAccess<'l> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'l>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        match (self.names.next(), self.values.next()) {
+            (Some(name), Some(value)) => {
+                self.value = Some(get(value));
+                seed.deserialize(BorrowedStrDeserializer::new(name))
+                    .map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'l>>(&mut self, seed: V) -> Result<V::Value> {
+        match self.value.take() {
+            Some(value) => seed.deserialize(ValueDeserializer(value)),
+            _ => raise!("expected a key before a value"),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+struct ValueSeqAccess<'l>(std::slice::Iter<'l, OnceCell<Value>>);
+
+impl<'l> SeqAccess<'l> for ValueSeqAccess<'l> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'l>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.0.next() {
+            Some(value) => seed.deserialize(ValueDeserializer(get(value))).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct ValueDeserializer<'l>(&'l Value);
+
+impl<'l> Deserializer<'l> for ValueDeserializer<'l> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'l>>(self, visitor: V) -> Result<V::Value> {
+        match self.0 {
+            Value::Binary(ref value) => visitor.visit_borrowed_bytes(value),
+            Value::Float(value) => visitor.visit_f64(*value),
+            Value::Integer(value) => visitor.visit_i64(*value),
+            Value::String(ref value) => visitor.visit_borrowed_str(value),
+            Value::Null => visitor.visit_unit(),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'l>>(self, visitor: V) -> Result<V::Value> {
+        match self.0 {
+            Value::Integer(value) => visitor.visit_bool(*value != 0),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'l>>(self, visitor: V) -> Result<V::Value> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V: Visitor<'l>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'l>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        match self.0 {
+            Value::String(ref value) => {
+                visitor.visit_enum(IntoDeserializer::<Error>::into_deserializer(value.as_str()))
+            }
+            _ => raise!(
+                "expected a string for an enum but found {:?}",
+                self.0.kind()
+            ),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        <V: Visitor<'l>>
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct ParameterSerializer<'l, 'm> {
+    statement: &'m mut Statement<'l>,
+}
+
+struct ParameterMapSerializer<'l, 'm> {
+    statement: &'m mut Statement<'l>,
+    key: Option<String>,
+}
+
+struct ParameterSeqSerializer<'l, 'm> {
+    statement: &'m mut Statement<'l>,
+    index: usize,
+}
+
+fn name(key: &str) -> String {
+    if key.starts_with([':', '@', '$', '?']) {
+        key.to_string()
+    } else {
+        format!(":{key}")
+    }
+}
+
+macro_rules! unsupported(
+    ($($method:ident($($argument:ty),*) -> $output:ty,)*) => {
+        $(
+            fn $method(self, $(_: $argument),*) -> Result<$output> {
+                raise!("expected a struct, a map, or a sequence of parameters");
+            }
+        )*
+    };
+);
+
+impl<'l, 'm> Serializer for ParameterSerializer<'l, 'm> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = ParameterSeqSerializer<'l, 'm>;
+    type SerializeTuple = ParameterSeqSerializer<'l, 'm>;
+    type SerializeTupleStruct = ParameterSeqSerializer<'l, 'm>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = ParameterMapSerializer<'l, 'm>;
+    type SerializeStruct = ParameterMapSerializer<'l, 'm>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    #[inline]
+    fn serialize_none(self) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<()> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _: &'static str) -> Result<()> {
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(ParameterSeqSerializer {
+            statement: self.statement,
+            index: 0,
+        })
+    }
+
+    #[inline]
+    fn serialize_tuple(self, length: usize) -> Result<Self::SerializeTuple> {
+        self.serialize_seq(Some(length))
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        length: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.serialize_seq(Some(length))
+    }
+
+    #[inline]
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(ParameterMapSerializer {
+            statement: self.statement,
+            key: None,
+        })
+    }
+
+    #[inline]
+    fn serialize_struct(self, _: &'static str, length: usize) -> Result<Self::SerializeStruct> {
+        self.serialize_map(Some(length))
+    }
+
+    unsupported! {
+        serialize_bool(bool) -> (),
+        serialize_i8(i8) -> (),
+        serialize_i16(i16) -> (),
+        serialize_i32(i32) -> (),
+        serialize_i64(i64) -> (),
+        serialize_u8(u8) -> (),
+        serialize_u16(u16) -> (),
+        serialize_u32(u32) -> (),
+        serialize_u64(u64) -> (),
+        serialize_f32(f32) -> (),
+        serialize_f64(f64) -> (),
+        serialize_char(char) -> (),
+        serialize_str(&str) -> (),
+        serialize_bytes(&[u8]) -> (),
+        serialize_unit_variant(&'static str, u32, &'static str) -> (),
+        serialize_tuple_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeTupleVariant,
+        serialize_struct_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeStructVariant,
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<()> {
+        raise!("expected a struct, a map, or a sequence of parameters");
+    }
+}
+
+impl ser::SerializeMap for ParameterMapSerializer<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<()> {
+        match key.serialize(ValueSerializer)? {
+            Value::String(key) => self.key = Some(name(&key)),
+            _ => raise!("expected a string as a parameter name"),
+        }
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        let value = value.serialize(ValueSerializer)?;
+        match self.key.take() {
+            Some(key) => value.bind(self.statement, key.as_str()),
+            _ => raise!("expected a parameter name before a value"),
+        }
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for ParameterMapSerializer<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        value
+            .serialize(ValueSerializer)?
+            .bind(self.statement, name(key).as_str())
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for ParameterSeqSerializer<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        self.index += 1;
+        value
+            .serialize(ValueSerializer)?
+            .bind(self.statement, self.index)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for ParameterSeqSerializer<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for ParameterSeqSerializer<'_, '_> {
+    type Ok = ();
+    type Error = Error;
+
+    #[inline]
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
+
+struct ValueSerializer;
+
+macro_rules! serialize_nested(
+    ($($method:ident($($argument:ty),*) -> $output:ty,)*) => {
+        $(
+            fn $method(self, $(_: $argument),*) -> Result<$output> {
+                raise!("cannot bind a nested value");
+            }
+        )*
+    };
+);
+
+impl Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+    type SerializeSeq = Impossible<Value, Error>;
+    type SerializeTuple = Impossible<Value, Error>;
+    type SerializeTupleStruct = Impossible<Value, Error>;
+    type SerializeTupleVariant = Impossible<Value, Error>;
+    type SerializeMap = Impossible<Value, Error>;
+    type SerializeStruct = Impossible<Value, Error>;
+    type SerializeStructVariant = Impossible<Value, Error>;
+
+    #[inline]
+    fn serialize_bool(self, value: bool) -> Result<Value> {
+        Ok(Value::Integer(value as i64))
+    }
+
+    #[inline]
+    fn serialize_i8(self, value: i8) -> Result<Value> {
+        Ok(Value::Integer(value.into()))
+    }
+
+    #[inline]
+    fn serialize_i16(self, value: i16) -> Result<Value> {
+        Ok(Value::Integer(value.into()))
+    }
+
+    #[inline]
+    fn serialize_i32(self, value: i32) -> Result<Value> {
+        Ok(Value::Integer(value.into()))
+    }
+
+    #[inline]
+    fn serialize_i64(self, value: i64) -> Result<Value> {
+        Ok(Value::Integer(value))
+    }
+
+    #[inline]
+    fn serialize_u8(self, value: u8) -> Result<Value> {
+        Ok(Value::Integer(value.into()))
+    }
+
+    #[inline]
+    fn serialize_u16(self, value: u16) -> Result<Value> {
+        Ok(Value::Integer(value.into()))
+    }
+
+    #[inline]
+    fn serialize_u32(self, value: u32) -> Result<Value> {
+        Ok(Value::Integer(value.into()))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<Value> {
+        match i64::try_from(value) {
+            Ok(value) => Ok(Value::Integer(value)),
+            _ => Err(crate::error::out_of_range(value)),
+        }
+    }
+
+    #[inline]
+    fn serialize_f32(self, value: f32) -> Result<Value> {
+        Ok(Value::Float(value.into()))
+    }
+
+    #[inline]
+    fn serialize_f64(self, value: f64) -> Result<Value> {
+        Ok(Value::Float(value))
+    }
+
+    #[inline]
+    fn serialize_char(self, value: char) -> Result<Value> {
+        Ok(Value::String(value.to_string()))
+    }
+
+    #[inline]
+    fn serialize_str(self, value: &str) -> Result<Value> {
+        Ok(Value::String(value.into()))
+    }
+
+    #[inline]
+    fn serialize_bytes(self, value: &[u8]) -> Result<Value> {
+        Ok(Value::Binary(value.into()))
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Value> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Value> {
+        Ok(Value::String(variant.into()))
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Value> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Value> {
+        raise!("cannot bind a nested value");
+    }
+
+    serialize_nested! {
+        serialize_seq(Option<usize>) -> Self::SerializeSeq,
+        serialize_tuple(usize) -> Self::SerializeTuple,
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct,
+        serialize_tuple_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeTupleVariant,
+        serialize_map(Option<usize>) -> Self::SerializeMap,
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct,
+        serialize_struct_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeStructVariant,
+    }
+}
@@ -0,0 +1,263 @@
+use ::serde_json::{Map, Number, Value as Json};
+
+use crate::error::{Error, Result};
+use crate::statement::{
+    BindableWithIndex, ColumnIndex, ParameterIndex, ReadableWithIndex, Statement,
+};
+use crate::value::Value;
+
+impl BindableWithIndex for Json {
+    #[inline]
+    fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+        (&self).bind(statement, index)
+    }
+}
+
+impl BindableWithIndex for &Json {
+    #[inline]
+    fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+        self.to_string().bind(statement, index)
+    }
+}
+
+impl From<Json> for Value {
+    #[inline]
+    fn from(value: Json) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl ReadableWithIndex for Json {
+    #[inline]
+    fn read<T: ColumnIndex>(statement: &Statement, index: T) -> Result<Self> {
+        Json::try_from(&Value::read(statement, index)?)
+    }
+}
+
+impl TryFrom<&Value> for Json {
+    type Error = Error;
+
+    /// Convert a string containing JSON text, a blob containing [JSONB][1], or a scalar.
+    ///
+    /// Strings are always parsed as JSON text. In particular, a string extracted via
+    /// `json_extract` is plain text and fails to be converted unless it happens to be valid JSON;
+    /// it is to be read as `String` instead, or it is to be extracted via the `->` operator, which
+    /// returns JSON text.
+    ///
+    /// [1]: https://sqlite.org/jsonb.html
+    fn try_from(value: &Value) -> Result<Self> {
+        match value {
+            Value::String(ref value) => match ::serde_json::from_str(value) {
+                Ok(value) => Ok(value),
+                Err(error) => raise!("failed to parse JSON ({error})"),
+            },
+            Value::Binary(ref value) => match decode(value, 0)? {
+                (json, size) if size == value.len() => Ok(json),
+                _ => raise!("failed to parse JSONB (trailing bytes)"),
+            },
+            &Value::Float(value) => match Number::from_f64(value) {
+                Some(value) => Ok(Json::Number(value)),
+                _ => raise!("failed to convert a float to JSON ({value})"),
+            },
+            &Value::Integer(value) => Ok(Json::Number(value.into())),
+            Value::Null => Ok(Json::Null),
+        }
+    }
+}
+
+impl TryFrom<&Value> for Option<Json> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &Value) -> Result<Self> {
+        if let Value::Null = value {
+            return Ok(None);
+        }
+        Json::try_from(value).map(Some)
+    }
+}
+
+// The maximum nesting depth of arrays and objects, which is the same as the one of serde_json
+// for JSON text.
+const MAX_DEPTH: usize = 128;
+
+// Decode an element and return it together with the number of bytes consumed.
+fn decode(bytes: &[u8], depth: usize) -> Result<(Json, usize)> {
+    let (kind, size, offset) = match decode_header(bytes) {
+        Some(header) => header,
+        _ => raise!("failed to parse JSONB"),
+    };
+    let end = match offset.checked_add(size) {
+        Some(end) => end,
+        _ => raise!("failed to parse JSONB"),
+    };
+    let payload = match bytes.get(offset..end) {
+        Some(payload) => payload,
+        _ => raise!("failed to parse JSONB"),
+    };
+    if matches!(kind, 11 | 12) && depth >= MAX_DEPTH {
+        raise!("failed to parse JSONB (too deep)");
+    }
+    let value = match kind {
+        0 => Json::Null,
+        1 => Json::Bool(true),
+        2 => Json::Bool(false),
+        3 | 5 => parse(payload)?,
+        4 => decode_int5(payload)?,
+        6 => decode_float5(payload)?,
+        7 | 10 => Json::String(decode_text(payload)?.into()),
+        8 => parse(&[b"\"", payload, b"\""].concat())?,
+        9 => parse(&decode_text5(decode_text(payload)?).into_bytes())?,
+        11 => {
+            let mut values = Vec::new();
+            let mut position = 0;
+            while position < payload.len() {
+                let (value, size) = decode(&payload[position..], depth + 1)?;
+                values.push(value);
+                position += size;
+            }
+            Json::Array(values)
+        }
+        12 => {
+            let mut values = Map::new();
+            let mut position = 0;
+            while position < payload.len() {
+                let (key, size) = decode(&payload[position..], depth + 1)?;
+                position += size;
+                let key = match key {
+                    Json::String(key) => key,
+                    _ => raise!("failed to parse JSONB (a key is not a string)"),
+                };
+                let (value, size) = decode(&payload[position..], depth + 1)?;
+                position += size;
+                values.insert(key, value);
+            }
+            Json::Object(values)
+        }
+        _ => raise!("failed to parse JSONB (unknown element type {kind})"),
+    };
+    Ok((value, end))
+}
+
+// Decode the type, the payload size, and the header size of an element.
+fn decode_header(bytes: &[u8]) -> Option<(u8, usize, usize)> {
+    let first = *bytes.first()?;
+    let (size, offset) = match first >> 4 {
+        size @ 0..=11 => (size as u64, 1),
+        12 => (*bytes.get(1)? as u64, 2),
+        13 => (
+            u16::from_be_bytes(bytes.get(1..3)?.try_into().ok()?) as u64,
+            3,
+        ),
+        14 => (
+            u32::from_be_bytes(bytes.get(1..5)?.try_into().ok()?) as u64,
+            5,
+        ),
+        _ => (u64::from_be_bytes(bytes.get(1..9)?.try_into().ok()?), 9),
+    };
+    Some((first & 0x0f, usize::try_from(size).ok()?, offset))
+}
+
+fn decode_int5(payload: &[u8]) -> Result<Json> {
+    let text = decode_text(payload)?;
+    let (negative, digits) = match text.as_bytes().first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+    let (digits, radix) = match digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        Some(digits) => (digits, 16),
+        _ => (digits, 10),
+    };
+    // The parser accepts a sign of its own, which is not to follow the one already stripped.
+    if !digits.starts_with(|c: char| c.is_ascii_hexdigit()) {
+        raise!("failed to parse JSONB ({text})");
+    }
+    let value = match i128::from_str_radix(digits, radix) {
+        Ok(value) if negative => value.checked_neg(),
+        Ok(value) => Some(value),
+        _ => None,
+    };
+    let value = match value {
+        Some(value) => value,
+        _ => raise!("failed to parse JSONB ({text})"),
+    };
+    if let Ok(value) = i64::try_from(value) {
+        Ok(Json::Number(value.into()))
+    } else if let Ok(value) = u64::try_from(value) {
+        Ok(Json::Number(value.into()))
+    } else {
+        Err(crate::error::out_of_range(value))
+    }
+}
+
+fn decode_float5(payload: &[u8]) -> Result<Json> {
+    let text = decode_text(payload)?;
+    let (sign, digits) = match text.as_bytes().first() {
+        Some(b'-') => ("-", &text[1..]),
+        Some(b'+') => ("", &text[1..]),
+        _ => ("", text),
+    };
+    let mut normalized = String::from(sign);
+    if digits.starts_with('.') {
+        normalized.push('0');
+    }
+    let mut characters = digits.chars().peekable();
+    while let Some(character) = characters.next() {
+        normalized.push(character);
+        if character == '.' && !matches!(characters.peek(), Some('0'..='9')) {
+            normalized.push('0');
+        }
+    }
+    parse(normalized.as_bytes())
+}
+
+fn decode_text(payload: &[u8]) -> Result<&str> {
+    match std::str::from_utf8(payload) {
+        Ok(text) => Ok(text),
+        _ => raise!("failed to parse JSONB (invalid UTF-8)"),
+    }
+}
+
+// Translate a string with JSON5 escapes into a quoted string with JSON escapes.
+fn decode_text5(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() + 2);
+    result.push('"');
+    let mut characters = text.chars().peekable();
+    while let Some(character) = characters.next() {
+        match character {
+            '"' => result.push_str("\\\""),
+            '\\' => match characters.next() {
+                Some('\'') => result.push('\''),
+                Some('v') => result.push_str("\\u000b"),
+                Some('0') => result.push_str("\\u0000"),
+                Some('x') => {
+                    result.push_str("\\u00");
+                    result.extend(characters.by_ref().take(2));
+                }
+                Some('\r') => {
+                    characters.next_if_eq(&'\n');
+                }
+                Some('\n' | '\u{2028}' | '\u{2029}') => {}
+                Some(character) => {
+                    result.push('\\');
+                    result.push(character);
+                }
+                None => result.push('\\'),
+            },
+            character => result.push(character),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn parse(text: &[u8]) -> Result<Json> {
+    match ::serde_json::from_slice(text) {
+        Ok(value) => Ok(value),
+        Err(error) => raise!("failed to parse JSONB ({error})"),
+    }
+}
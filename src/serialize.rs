@@ -0,0 +1,239 @@
+use std::ops::Deref;
+
+use crate::connection::Connection;
+use crate::error::Result;
+
+/// A serialized database.
+///
+/// The content is allocated and freed by SQLite. Converting a slice or a vector copies it; if the
+/// allocation fails, the result is empty, and the failure is reported by
+/// `Connection::deserialize`.
+pub struct SerializedDb {
+    data: *mut u8,
+    size: usize,
+}
+
+/// A mode of deserialization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeserializeMode {
+    /// Allow only reading.
+    ReadOnly,
+    /// Allow reading and writing, growing the database as needed.
+    Resizeable,
+}
+
+impl SerializedDb {
+    /// Return the size in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        if self.data.is_null() {
+            0
+        } else {
+            self.size
+        }
+    }
+
+    /// Check if the database is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl From<&[u8]> for SerializedDb {
+    fn from(value: &[u8]) -> Self {
+        let data = unsafe { ffi::sqlite3_malloc64(value.len() as u64) as *mut u8 };
+        // The size is kept in order to tell a failed allocation from an empty database.
+        if data.is_null() {
+            return Self {
+                data,
+                size: value.len(),
+            };
+        }
+        unsafe { std::ptr::copy_nonoverlapping(value.as_ptr(), data, value.len()) };
+        Self {
+            data,
+            size: value.len(),
+        }
+    }
+}
+
+impl From<Vec<u8>> for SerializedDb {
+    #[inline]
+    fn from(value: Vec<u8>) -> Self {
+        value.as_slice().into()
+    }
+}
+
+impl From<&Vec<u8>> for SerializedDb {
+    #[inline]
+    fn from(value: &Vec<u8>) -> Self {
+        value.as_slice().into()
+    }
+}
+
+impl Deref for SerializedDb {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        if self.data.is_null() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.data, self.size) }
+        }
+    }
+}
+
+impl AsRef<[u8]> for SerializedDb {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl std::fmt::Debug for SerializedDb {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter
+            .debug_struct("SerializedDb")
+            .field("size", &self.len())
+            .finish()
+    }
+}
+
+impl Drop for SerializedDb {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { ffi::sqlite3_free(self.data as *mut _) };
+    }
+}
+
+unsafe impl Send for SerializedDb {}
+
+unsafe impl Sync for SerializedDb {}
+
+impl Connection {
+    /// Serialize a database.
+    ///
+    /// The result contains the same bytes as the database would have if it was written to disk.
+    /// The schema is `main` for the main database or the name of an attached one. Depending on
+    /// the version of SQLite, a database that has never been written to is serialized either as
+    /// no data or as a single page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let connection = sqlite::open(":memory:")?;
+    /// connection.execute("CREATE TABLE users (name TEXT)")?;
+    /// let data = connection.serialize("main")?;
+    /// assert!(data.starts_with(b"SQLite format 3\0"));
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    pub fn serialize(&self, schema: &str) -> Result<SerializedDb> {
+        let schema = str_to_cstr!(schema);
+        let mut size = 0;
+        let data = unsafe { ffi::sqlite3_serialize(self.as_raw(), schema.as_ptr(), &mut size, 0) };
+        if data.is_null() && size != 0 {
+            raise!("failed to serialize the database");
+        }
+        Ok(SerializedDb {
+            data,
+            size: size as usize,
+        })
+    }
+
+    /// Serialize a database without copying.
+    ///
+    /// The result refers to the memory of the database directly and is available only for
+    /// databases held in memory contiguously, such as those that have been deserialized;
+    /// otherwise, `None` is returned.
+    pub fn serialize_no_copy(&mut self, schema: &str) -> Result<Option<&[u8]>> {
+        let schema = str_to_cstr!(schema);
+        let mut size = 0;
+        let data = unsafe {
+            ffi::sqlite3_serialize(
+                self.as_raw(),
+                schema.as_ptr(),
+                &mut size,
+                ffi::SQLITE_SERIALIZE_NOCOPY as _,
+            )
+        };
+        if size < 0 {
+            raise!("failed to serialize the database");
+        }
+        if data.is_null() {
+            return Ok(if size == 0 { Some(&[]) } else { None });
+        }
+        Ok(Some(unsafe {
+            std::slice::from_raw_parts(data, size as usize)
+        }))
+    }
+
+    /// Deserialize a database.
+    ///
+    /// The database replaces the one with the given schema, which is `main` for the main
+    /// database or the name of an attached one, and is held in memory owned by SQLite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite::DeserializeMode;
+    ///
+    /// let connection = sqlite::open(":memory:")?;
+    /// connection.execute("CREATE TABLE users (name TEXT)")?;
+    /// let data = connection.serialize("main")?;
+    ///
+    /// let connection = sqlite::open(":memory:")?;
+    /// connection.deserialize("main", &*data, DeserializeMode::Resizeable)?;
+    /// connection.execute("INSERT INTO users VALUES ('Alice')")?;
+    /// assert!(connection.serialize("main")?.len() >= data.len());
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    pub fn deserialize<T: Into<SerializedDb>>(
+        &self,
+        schema: &str,
+        data: T,
+        mode: DeserializeMode,
+    ) -> Result<()> {
+        let schema = str_to_cstr!(schema);
+        let data = data.into();
+        if data.data.is_null() && data.size > 0 {
+            raise!("failed to allocate memory");
+        }
+        let flags = ffi::SQLITE_DESERIALIZE_FREEONCLOSE
+            | match mode {
+                DeserializeMode::ReadOnly => ffi::SQLITE_DESERIALIZE_READONLY,
+                DeserializeMode::Resizeable => ffi::SQLITE_DESERIALIZE_RESIZEABLE,
+            };
+        // The memory is freed by SQLite even in case of failure.
+        let data = std::mem::ManuallyDrop::new(data);
+        unsafe {
+            ok!(
+                self.as_raw(),
+                ffi::sqlite3_deserialize(
+                    self.as_raw(),
+                    schema.as_ptr(),
+                    data.data,
+                    data.size as i64,
+                    data.size as i64,
+                    flags as _,
+                )
+            );
+        }
+        Ok(())
+    }
+
+    /// Deserialize the main database for reading only.
+    #[deprecated(note = "use `deserialize` instead")]
+    #[inline]
+    pub fn deserialize_to_readonly_db(&self, data: &[u8]) -> Result<()> {
+        self.deserialize("main", data, DeserializeMode::ReadOnly)
+    }
+
+    /// Serialize the main database into a buffer.
+    #[deprecated(note = "use `serialize` instead")]
+    #[inline]
+    pub async fn serialize_to_buffer(&mut self) -> Result<Vec<u8>> {
+        self.serialize("main").map(|data| data.to_vec())
+    }
+}
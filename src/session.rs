@@ -0,0 +1,615 @@
+use core::ffi::{c_char, c_int, c_void};
+use std::io::Write;
+use std::marker::PhantomData;
+
+use crate::connection::Connection;
+use crate::error::Result;
+use crate::value::Value;
+
+/// A session recording changes to a database.
+///
+/// Changes made via the connection to the attached tables are recorded and can be extracted as
+/// a changeset or a patchset, which can then be applied to another database via
+/// `Connection::apply_changeset`. Only tables with a primary key are recorded.
+///
+/// The functionality requires SQLite to be compiled with `SQLITE_ENABLE_SESSION` and
+/// `SQLITE_ENABLE_PREUPDATE_HOOK`. With the `bundled` feature, these are taken from the
+/// environment variables of the same names when SQLite is being built.
+///
+/// # Examples
+///
+/// ```
+/// use sqlite::{Changeset, Operation, Session, Value};
+/// # let connection = sqlite::open(":memory:").unwrap();
+/// # connection.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)");
+/// let mut session = Session::new(&connection, "main")?;
+/// session.attach("users")?;
+/// connection.execute("INSERT INTO users VALUES (1, 'Alice')")?;
+/// let changeset = session.changeset()?;
+///
+/// let change = Changeset::new(&changeset)?.next().unwrap()?;
+/// assert_eq!(change.operation, Operation::Insert);
+/// assert_eq!(change.table, "users");
+/// assert_eq!(change.new[1], Some(Value::String("Alice".into())));
+/// # Ok::<(), sqlite::Error>(())
+/// ```
+pub struct Session<'l> {
+    raw: *mut sqlite3_session,
+    phantom: PhantomData<&'l Connection>,
+}
+
+/// An iterator over the changes of a changeset or a patchset.
+pub struct Changeset<'l> {
+    raw: *mut sqlite3_changeset_iter,
+    done: bool,
+    phantom: PhantomData<&'l [u8]>,
+}
+
+/// A change of a row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    /// The operation.
+    pub operation: Operation,
+    /// The name of the table.
+    pub table: String,
+    /// The flag indicating that the change is indirect.
+    pub indirect: bool,
+    /// The values before the change.
+    ///
+    /// The values are absent for insertions and, in case of updates, for unchanged columns.
+    /// Patchsets retain only the primary key of deleted rows.
+    pub old: Vec<Option<Value>>,
+    /// The values after the change.
+    ///
+    /// The values are absent for deletions and, in case of updates, for unchanged columns.
+    pub new: Vec<Option<Value>>,
+}
+
+/// An operation on a row.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operation {
+    /// An insertion.
+    Insert,
+    /// An update.
+    Update,
+    /// A deletion.
+    Delete,
+}
+
+/// A conflict encountered when applying a changeset.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConflictType {
+    /// A row to be updated or deleted does not have the expected values.
+    Data,
+    /// A row to be updated or deleted does not exist.
+    NotFound,
+    /// A row to be inserted has the same primary key as an existing row.
+    Conflict,
+    /// A change violates a constraint.
+    Constraint,
+    /// The foreign key constraints are violated after applying the changeset, which is reported
+    /// once with the number of violations and without a change.
+    ForeignKey(usize),
+}
+
+/// An action to be taken in case of a conflict.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConflictAction {
+    /// Skip the change.
+    Omit,
+    /// Apply the change anyway, which is only possible for `ConflictType::Data` and
+    /// `ConflictType::Conflict`.
+    Replace,
+    /// Abort and roll back the whole changeset.
+    Abort,
+}
+
+impl<'l> Session<'l> {
+    /// Create a session for a database, such as `main` or the name of an attached database.
+    pub fn new<T: AsRef<str>>(connection: &'l Connection, database: T) -> Result<Session<'l>> {
+        let mut raw = std::ptr::null_mut();
+        unsafe {
+            ok!(
+                connection.as_raw(),
+                sqlite3session_create(
+                    connection.as_raw(),
+                    str_to_cstr!(database.as_ref()).as_ptr(),
+                    &mut raw,
+                )
+            );
+        }
+        Ok(Session {
+            raw,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Start recording changes to a table.
+    pub fn attach<T: AsRef<str>>(&mut self, table: T) -> Result<()> {
+        unsafe {
+            ok!(sqlite3session_attach(
+                self.raw,
+                str_to_cstr!(table.as_ref()).as_ptr(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Start recording changes to all tables.
+    pub fn attach_all(&mut self) -> Result<()> {
+        unsafe {
+            ok!(sqlite3session_attach(self.raw, std::ptr::null()));
+        }
+        Ok(())
+    }
+
+    /// Enable or disable recording.
+    #[inline]
+    pub fn set_enabled(&mut self, value: bool) {
+        unsafe {
+            sqlite3session_enable(self.raw, value as c_int);
+        }
+    }
+
+    /// Mark subsequent changes as indirect or direct.
+    #[inline]
+    pub fn set_indirect(&mut self, value: bool) {
+        unsafe {
+            sqlite3session_indirect(self.raw, value as c_int);
+        }
+    }
+
+    /// Check if no changes have been recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        unsafe { sqlite3session_isempty(self.raw) != 0 }
+    }
+
+    /// Return the recorded changes as a changeset.
+    #[inline]
+    pub fn changeset(&self) -> Result<Vec<u8>> {
+        unsafe { output(|size, data| sqlite3session_changeset(self.raw, size, data)) }
+    }
+
+    /// Return the recorded changes as a patchset.
+    ///
+    /// A patchset is a more compact changeset that omits the original values of updated and
+    /// deleted rows, except for the primary key.
+    #[inline]
+    pub fn patchset(&self) -> Result<Vec<u8>> {
+        unsafe { output(|size, data| sqlite3session_patchset(self.raw, size, data)) }
+    }
+
+    /// Write the recorded changes as a changeset.
+    #[inline]
+    pub fn write_changeset<W: Write>(&self, writer: W) -> Result<()> {
+        unsafe {
+            stream(writer, |callback, context| {
+                sqlite3session_changeset_strm(self.raw, callback, context)
+            })
+        }
+    }
+
+    /// Write the recorded changes as a patchset.
+    #[inline]
+    pub fn write_patchset<W: Write>(&self, writer: W) -> Result<()> {
+        unsafe {
+            stream(writer, |callback, context| {
+                sqlite3session_patchset_strm(self.raw, callback, context)
+            })
+        }
+    }
+}
+
+impl Drop for Session<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { sqlite3session_delete(self.raw) };
+    }
+}
+
+impl<'l> Changeset<'l> {
+    /// Create an iterator over a changeset or a patchset.
+    pub fn new(changeset: &'l [u8]) -> Result<Changeset<'l>> {
+        let mut raw = std::ptr::null_mut();
+        unsafe {
+            ok!(sqlite3changeset_start(
+                &mut raw,
+                changeset.len() as c_int,
+                changeset.as_ptr() as *mut _,
+            ));
+        }
+        Ok(Changeset {
+            raw,
+            done: false,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Invert a changeset, which turns insertions into deletions and vice versa and swaps the
+    /// old and new values of updates.
+    ///
+    /// Patchsets cannot be inverted.
+    pub fn invert(changeset: &[u8]) -> Result<Vec<u8>> {
+        unsafe {
+            output(|size, data| {
+                sqlite3changeset_invert(
+                    changeset.len() as c_int,
+                    changeset.as_ptr() as *const _,
+                    size,
+                    data,
+                )
+            })
+        }
+    }
+
+    /// Concatenate two changesets or two patchsets into one.
+    pub fn concat(first: &[u8], second: &[u8]) -> Result<Vec<u8>> {
+        unsafe {
+            output(|size, data| {
+                sqlite3changeset_concat(
+                    first.len() as c_int,
+                    first.as_ptr() as *mut _,
+                    second.len() as c_int,
+                    second.as_ptr() as *mut _,
+                    size,
+                    data,
+                )
+            })
+        }
+    }
+}
+
+impl Iterator for Changeset<'_> {
+    type Item = Result<Change>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match unsafe { sqlite3changeset_next(self.raw) } {
+            ffi::SQLITE_ROW => {}
+            ffi::SQLITE_DONE => {
+                self.done = true;
+                return None;
+            }
+            code => {
+                self.done = true;
+                return Some(Err(crate::error::Error {
+                    code: Some(code as isize),
+                    message: None,
+                    kind: Default::default(),
+                }));
+            }
+        }
+        let result = unsafe { change(self.raw) };
+        if result.is_err() {
+            self.done = true;
+        }
+        Some(result)
+    }
+}
+
+impl Drop for Changeset<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { sqlite3changeset_finalize(self.raw) };
+    }
+}
+
+impl Connection {
+    /// Apply a changeset or a patchset.
+    ///
+    /// The filter is called with the name of each table in the changeset and decides whether the
+    /// changes to the table are applied. The conflict handler is called for each change that cannot
+    /// be applied cleanly and decides how to proceed; the change is absent for
+    /// `ConflictType::ForeignKey`, which concerns the changeset as a whole. The changes are applied
+    /// within a savepoint, which is rolled back if the handler aborts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite::{ConflictAction, Session};
+    /// # let source = sqlite::open(":memory:").unwrap();
+    /// # let target = sqlite::open(":memory:").unwrap();
+    /// # for connection in [&source, &target] {
+    /// #     connection.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)");
+    /// # }
+    /// let mut session = Session::new(&source, "main")?;
+    /// session.attach("users")?;
+    /// source.execute("INSERT INTO users VALUES (1, 'Alice')")?;
+    /// target.apply_changeset(&session.changeset()?, |_| true, |_, _| ConflictAction::Abort)?;
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    pub fn apply_changeset<F, G>(&self, changeset: &[u8], filter: F, conflict: G) -> Result<()>
+    where
+        F: FnMut(&str) -> bool,
+        G: FnMut(ConflictType, Option<&Change>) -> ConflictAction,
+    {
+        let mut handlers = (filter, conflict);
+        unsafe {
+            ok!(
+                self.as_raw(),
+                sqlite3changeset_apply(
+                    self.as_raw(),
+                    changeset.len() as c_int,
+                    changeset.as_ptr() as *mut _,
+                    Some(filter_callback::<F, G>),
+                    Some(conflict_callback::<F, G>),
+                    &mut handlers as *mut (F, G) as *mut _,
+                )
+            );
+        }
+        Ok(())
+    }
+}
+
+unsafe fn change(raw: *mut sqlite3_changeset_iter) -> Result<Change> {
+    let mut table = std::ptr::null();
+    let mut count = 0;
+    let mut operation = 0;
+    let mut indirect = 0;
+    ok!(sqlite3changeset_op(
+        raw,
+        &mut table,
+        &mut count,
+        &mut operation,
+        &mut indirect,
+    ));
+    let operation = match operation {
+        ffi::SQLITE_INSERT => Operation::Insert,
+        ffi::SQLITE_UPDATE => Operation::Update,
+        ffi::SQLITE_DELETE => Operation::Delete,
+        _ => raise!("found an unknown operation ({operation})"),
+    };
+    Ok(Change {
+        table: c_str_to_string!(table),
+        indirect: indirect != 0,
+        old: match operation {
+            Operation::Insert => vec![None; count as usize],
+            _ => values(raw, count, sqlite3changeset_old)?,
+        },
+        new: match operation {
+            Operation::Delete => vec![None; count as usize],
+            _ => values(raw, count, sqlite3changeset_new)?,
+        },
+        operation,
+    })
+}
+
+unsafe fn values(
+    raw: *mut sqlite3_changeset_iter,
+    count: c_int,
+    read: Read,
+) -> Result<Vec<Option<Value>>> {
+    let mut values = Vec::with_capacity(count as usize);
+    for index in 0..count {
+        let mut value = std::ptr::null_mut();
+        ok!(read(raw, index, &mut value));
+        values.push(if value.is_null() {
+            None
+        } else {
+            Some(crate::value::read(value))
+        });
+    }
+    Ok(values)
+}
+
+unsafe fn output<F>(produce: F) -> Result<Vec<u8>>
+where
+    F: FnOnce(*mut c_int, *mut *mut c_void) -> c_int,
+{
+    let mut size = 0;
+    let mut data = std::ptr::null_mut();
+    ok!(produce(&mut size, &mut data));
+    let result = if data.is_null() || size == 0 {
+        Vec::new()
+    } else {
+        std::slice::from_raw_parts(data as *const u8, size as usize).to_vec()
+    };
+    ffi::sqlite3_free(data);
+    Ok(result)
+}
+
+type Read =
+    unsafe extern "C" fn(*mut sqlite3_changeset_iter, c_int, *mut *mut ffi::sqlite3_value) -> c_int;
+
+type Output = Option<unsafe extern "C" fn(*mut c_void, *const c_void, c_int) -> c_int>;
+
+unsafe fn stream<W, F>(writer: W, produce: F) -> Result<()>
+where
+    W: Write,
+    F: FnOnce(Output, *mut c_void) -> c_int,
+{
+    let mut context = (writer, None::<std::io::Error>);
+    let code = produce(
+        Some(output_callback::<W>),
+        &mut context as *mut (W, _) as *mut _,
+    );
+    if let Some(error) = context.1 {
+        raise!("failed to write ({error})");
+    }
+    ok!(code);
+    match context.0.flush() {
+        Ok(_) => Ok(()),
+        Err(error) => raise!("failed to write ({error})"),
+    }
+}
+
+extern "C" fn output_callback<W>(context: *mut c_void, data: *const c_void, size: c_int) -> c_int
+where
+    W: Write,
+{
+    unsafe {
+        let context = &mut *(context as *mut (W, Option<std::io::Error>));
+        let data = if data.is_null() || size <= 0 {
+            &[][..]
+        } else {
+            std::slice::from_raw_parts(data as *const u8, size as usize)
+        };
+        match context.0.write_all(data) {
+            Ok(_) => ffi::SQLITE_OK,
+            Err(error) => {
+                context.1 = Some(error);
+                ffi::SQLITE_IOERR
+            }
+        }
+    }
+}
+
+extern "C" fn filter_callback<F, G>(context: *mut c_void, table: *const c_char) -> c_int
+where
+    F: FnMut(&str) -> bool,
+{
+    unsafe {
+        let handlers = &mut *(context as *mut (F, G));
+        c_int::from((handlers.0)(&c_str_to_string!(table)))
+    }
+}
+
+extern "C" fn conflict_callback<F, G>(
+    context: *mut c_void,
+    kind: c_int,
+    raw: *mut sqlite3_changeset_iter,
+) -> c_int
+where
+    G: FnMut(ConflictType, Option<&Change>) -> ConflictAction,
+{
+    unsafe {
+        let handlers = &mut *(context as *mut (F, G));
+        let kind = match kind {
+            SQLITE_CHANGESET_DATA => ConflictType::Data,
+            SQLITE_CHANGESET_NOTFOUND => ConflictType::NotFound,
+            SQLITE_CHANGESET_CONFLICT => ConflictType::Conflict,
+            SQLITE_CHANGESET_CONSTRAINT => ConflictType::Constraint,
+            SQLITE_CHANGESET_FOREIGN_KEY => {
+                let mut count = 0;
+                if sqlite3changeset_fk_conflicts(raw, &mut count) != ffi::SQLITE_OK {
+                    return SQLITE_CHANGESET_ABORT;
+                }
+                ConflictType::ForeignKey(count as usize)
+            }
+            _ => return SQLITE_CHANGESET_ABORT,
+        };
+        let change = match kind {
+            ConflictType::ForeignKey(_) => None,
+            _ => match change(raw) {
+                Ok(change) => Some(change),
+                _ => return SQLITE_CHANGESET_ABORT,
+            },
+        };
+        match (handlers.1)(kind, change.as_ref()) {
+            ConflictAction::Omit => SQLITE_CHANGESET_OMIT,
+            ConflictAction::Replace => SQLITE_CHANGESET_REPLACE,
+            ConflictAction::Abort => SQLITE_CHANGESET_ABORT,
+        }
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[repr(C)]
+struct sqlite3_session {
+    _private: [u8; 0],
+}
+
+#[allow(non_camel_case_types)]
+#[repr(C)]
+struct sqlite3_changeset_iter {
+    _private: [u8; 0],
+}
+
+const SQLITE_CHANGESET_DATA: c_int = 1;
+const SQLITE_CHANGESET_NOTFOUND: c_int = 2;
+const SQLITE_CHANGESET_CONFLICT: c_int = 3;
+const SQLITE_CHANGESET_CONSTRAINT: c_int = 4;
+const SQLITE_CHANGESET_FOREIGN_KEY: c_int = 5;
+
+const SQLITE_CHANGESET_OMIT: c_int = 0;
+const SQLITE_CHANGESET_REPLACE: c_int = 1;
+const SQLITE_CHANGESET_ABORT: c_int = 2;
+
+// The session extension is not covered by the bindings.
+extern "C" {
+    fn sqlite3session_create(
+        db: *mut ffi::sqlite3,
+        database: *const c_char,
+        session: *mut *mut sqlite3_session,
+    ) -> c_int;
+    fn sqlite3session_delete(session: *mut sqlite3_session);
+    fn sqlite3session_enable(session: *mut sqlite3_session, enable: c_int) -> c_int;
+    fn sqlite3session_indirect(session: *mut sqlite3_session, indirect: c_int) -> c_int;
+    fn sqlite3session_attach(session: *mut sqlite3_session, table: *const c_char) -> c_int;
+    fn sqlite3session_isempty(session: *mut sqlite3_session) -> c_int;
+    fn sqlite3session_changeset(
+        session: *mut sqlite3_session,
+        size: *mut c_int,
+        data: *mut *mut c_void,
+    ) -> c_int;
+    fn sqlite3session_patchset(
+        session: *mut sqlite3_session,
+        size: *mut c_int,
+        data: *mut *mut c_void,
+    ) -> c_int;
+    fn sqlite3session_changeset_strm(
+        session: *mut sqlite3_session,
+        output: Output,
+        context: *mut c_void,
+    ) -> c_int;
+    fn sqlite3session_patchset_strm(
+        session: *mut sqlite3_session,
+        output: Output,
+        context: *mut c_void,
+    ) -> c_int;
+    fn sqlite3changeset_start(
+        iterator: *mut *mut sqlite3_changeset_iter,
+        size: c_int,
+        data: *mut c_void,
+    ) -> c_int;
+    fn sqlite3changeset_next(iterator: *mut sqlite3_changeset_iter) -> c_int;
+    fn sqlite3changeset_op(
+        iterator: *mut sqlite3_changeset_iter,
+        table: *mut *const c_char,
+        count: *mut c_int,
+        operation: *mut c_int,
+        indirect: *mut c_int,
+    ) -> c_int;
+    fn sqlite3changeset_old(
+        iterator: *mut sqlite3_changeset_iter,
+        index: c_int,
+        value: *mut *mut ffi::sqlite3_value,
+    ) -> c_int;
+    fn sqlite3changeset_new(
+        iterator: *mut sqlite3_changeset_iter,
+        index: c_int,
+        value: *mut *mut ffi::sqlite3_value,
+    ) -> c_int;
+    fn sqlite3changeset_fk_conflicts(
+        iterator: *mut sqlite3_changeset_iter,
+        count: *mut c_int,
+    ) -> c_int;
+    fn sqlite3changeset_finalize(iterator: *mut sqlite3_changeset_iter) -> c_int;
+    fn sqlite3changeset_invert(
+        size: c_int,
+        data: *const c_void,
+        output_size: *mut c_int,
+        output_data: *mut *mut c_void,
+    ) -> c_int;
+    fn sqlite3changeset_concat(
+        first_size: c_int,
+        first_data: *mut c_void,
+        second_size: c_int,
+        second_data: *mut c_void,
+        output_size: *mut c_int,
+        output_data: *mut *mut c_void,
+    ) -> c_int;
+    fn sqlite3changeset_apply(
+        db: *mut ffi::sqlite3,
+        size: c_int,
+        data: *mut c_void,
+        filter: Option<unsafe extern "C" fn(*mut c_void, *const c_char) -> c_int>,
+        conflict: Option<
+            unsafe extern "C" fn(*mut c_void, c_int, *mut sqlite3_changeset_iter) -> c_int,
+        >,
+        context: *mut c_void,
+    ) -> c_int;
+}
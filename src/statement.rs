@@ -1,19 +1,56 @@
 use core::ffi::{c_double, c_int};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::rc::Rc;
+use std::sync::Arc;
 
-use crate::cursor::{Cursor, CursorWithOwnership, Row};
+use crate::cursor::{Cursor, CursorWithOwnership, Row, RowsRef};
 use crate::error::Result;
-use crate::value::{Type, Value};
-
-// https://sqlite.org/c3ref/c_static.html
-macro_rules! transient(
-    () => (
-        std::mem::transmute::<
-            *const std::ffi::c_void,
-            std::option::Option<unsafe extern "C" fn(*mut std::ffi::c_void)>
-        >(!0 as *const core::ffi::c_void)
+use crate::value::{Type, Value, ValueRef};
+
+/// Create parameters for binding to a prepared statement.
+///
+/// Each entry is either a value, which is bound to the parameter whose position matches the
+/// position of the entry, or a `name => value` pair, which is bound to the named parameter.
+///
+/// # Examples
+///
+/// ```
+/// use sqlite::params;
+/// # let connection = sqlite::open(":memory:").unwrap();
+/// # connection.execute("CREATE TABLE users (id INTEGER, name STRING)");
+/// let query = "SELECT * FROM users WHERE id = ? AND name = ?";
+/// let mut statement = connection.prepare(query)?;
+/// statement.bind(params![1, "Bob"])?;
+///
+/// let query = "SELECT * FROM users WHERE name = :name AND id = ?";
+/// let mut statement = connection.prepare(query)?;
+/// statement.bind(params![":name" => "Bob", 1])?;
+/// # Ok::<(), sqlite::Error>(())
+/// ```
+#[macro_export]
+macro_rules! params(
+    (@bind $statement:ident, $index:ident $(,)?) => (
+        let _ = $index;
+    );
+    (@bind $statement:ident, $index:ident, $name:expr => $value:expr $(, $($token:tt)*)?) => (
+        $index += 1;
+        $crate::BindableWithIndex::bind($value, $statement, $name)?;
+        $crate::params!(@bind $statement, $index $(, $($token)*)?);
+    );
+    (@bind $statement:ident, $index:ident, $value:expr $(, $($token:tt)*)?) => (
+        $index += 1;
+        $crate::BindableWithIndex::bind($value, $statement, $index)?;
+        $crate::params!(@bind $statement, $index $(, $($token)*)?);
+    );
+    () => (());
+    ($($token:tt)+) => (
+        $crate::Parameters(|statement: &mut $crate::Statement| -> $crate::Result<()> {
+            let mut index = 0usize;
+            $crate::params!(@bind statement, index, $($token)+);
+            Ok(())
+        })
     );
 );
 
@@ -31,6 +68,10 @@ pub trait Bindable {
     fn bind(self, _: &mut Statement) -> Result<()>;
 }
 
+/// Parameters produced by the `params!` macro.
+#[doc(hidden)]
+pub struct Parameters<F>(pub F);
+
 /// A type suitable for binding to a prepared statement given a parameter index.
 pub trait BindableWithIndex {
     /// Bind to a parameter.
@@ -39,6 +80,42 @@ pub trait BindableWithIndex {
     fn bind<T: ParameterIndex>(self, _: &mut Statement, _: T) -> Result<()>;
 }
 
+/// A type suitable for reading from a prepared statement via a borrowed value.
+///
+/// Any type implementing this trait is also `ReadableWithIndex`, which makes the trait a simpler
+/// alternative for custom types such as newtypes and enums stored as text.
+///
+/// # Examples
+///
+/// ```
+/// use sqlite::{FromSql, ValueRef};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct UserId(i64);
+///
+/// impl FromSql for UserId {
+///     fn from_sql(value: ValueRef<'_>) -> sqlite::Result<Self> {
+///         i64::try_from(value).map(UserId)
+///     }
+/// }
+///
+/// # let connection = sqlite::open(":memory:").unwrap();
+/// let mut statement = connection.prepare("SELECT 42")?;
+/// statement.next()?;
+/// assert_eq!(statement.read::<UserId, _>(0)?, UserId(42));
+/// # Ok::<(), sqlite::Error>(())
+/// ```
+pub trait FromSql: Sized {
+    /// Convert from a value.
+    fn from_sql(_: ValueRef<'_>) -> Result<Self>;
+}
+
+/// A type suitable for reading from a prepared statement given an entire row.
+pub trait FromRow: Sized {
+    /// Read from the current row.
+    fn from_row(_: &Statement) -> Result<Self>;
+}
+
 /// A type suitable for indexing columns in a prepared statement.
 pub trait ColumnIndex: Copy + std::fmt::Debug {
     /// Identify the ordinal position.
@@ -63,6 +140,52 @@ pub trait ReadableWithIndex: Sized {
     fn read<T: ColumnIndex>(_: &Statement, _: T) -> Result<Self>;
 }
 
+/// A type suitable for binding to a prepared statement via a borrowed or owned value.
+///
+/// Any type implementing this trait is also `BindableWithIndex`, which makes the trait a simpler
+/// alternative for custom types such as newtypes and enums stored as text.
+///
+/// # Examples
+///
+/// ```
+/// use sqlite::{ToSql, ToSqlOutput, ValueRef};
+///
+/// enum Role {
+///     Admin,
+///     Guest,
+/// }
+///
+/// impl ToSql for Role {
+///     fn to_sql(&self) -> sqlite::Result<ToSqlOutput<'_>> {
+///         Ok(ToSqlOutput::Borrowed(ValueRef::Text(match self {
+///             Role::Admin => "admin",
+///             Role::Guest => "guest",
+///         })))
+///     }
+/// }
+///
+/// # let connection = sqlite::open(":memory:").unwrap();
+/// # connection.execute("CREATE TABLE users (name TEXT, role TEXT)")?;
+/// let query = "INSERT INTO users VALUES (?, ?)";
+/// let mut statement = connection.prepare(query)?;
+/// statement.bind((1, "Alice"))?;
+/// statement.bind((2, Role::Admin))?;
+/// # Ok::<(), sqlite::Error>(())
+/// ```
+pub trait ToSql {
+    /// Convert to a value.
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>>;
+}
+
+/// A value produced by `ToSql`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ToSqlOutput<'l> {
+    /// A borrowed value.
+    Borrowed(ValueRef<'l>),
+    /// An owned value.
+    Owned(Value),
+}
+
 /// The state of a prepared statement.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum State {
@@ -72,6 +195,33 @@ pub enum State {
     Done,
 }
 
+/// A counter of a prepared statement.
+///
+/// See the [documentation][1] of SQLite for further details.
+///
+/// [1]: https://www.sqlite.org/c3ref/c_stmtstatus_counter.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatementStatus {
+    /// The number of steps taken in full table scans.
+    FullScanStep,
+    /// The number of sort operations.
+    Sort,
+    /// The number of rows inserted into transient indices created automatically.
+    AutoIndex,
+    /// The number of virtual machine operations.
+    VirtualMachineStep,
+    /// The number of times the statement has been automatically regenerated.
+    Reprepare,
+    /// The number of times the statement has been run.
+    Run,
+    /// The number of times a Bloom filter has failed to avoid a join step.
+    FilterMiss,
+    /// The number of times a Bloom filter has bypassed a join step.
+    FilterHit,
+    /// The approximate number of bytes of heap memory used to store the statement.
+    MemoryUsed,
+}
+
 impl<'l> Statement<'l> {
     /// Bind values to parameters.
     ///
@@ -115,6 +265,19 @@ impl<'l> Statement<'l> {
     /// # Ok::<(), sqlite::Error>(())
     /// ```
     ///
+    /// Tuples other than pairs and arrays are bound to consecutive positional parameters. Pairs
+    /// are always interpreted as an index followed by a value; use `params!` for binding two
+    /// positional parameters of different types.
+    ///
+    /// ```
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name STRING, age REAL)");
+    /// let query = "SELECT * FROM users WHERE id = ? AND name = ? AND age > ?";
+    /// let mut statement = connection.prepare(query)?;
+    /// statement.bind((1, "Bob", 42.0))?;
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    ///
     /// ```
     /// # use sqlite::Value;
     /// # let connection = sqlite::open(":memory:").unwrap();
@@ -127,6 +290,20 @@ impl<'l> Statement<'l> {
     /// ][..])?;
     /// # Ok::<(), sqlite::Error>(())
     /// ```
+    ///
+    /// Lists of values wrapped in `Rc` are bound as arrays, which are read via the `rarray`
+    /// table-valued function once it is registered via `Connection::enable_rarray`.
+    ///
+    /// ```
+    /// # use std::rc::Rc;
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name STRING)");
+    /// connection.enable_rarray()?;
+    /// let query = "SELECT * FROM users WHERE id IN rarray(?)";
+    /// let mut statement = connection.prepare(query)?;
+    /// statement.bind((1, Rc::new(vec![1i64, 2, 3])))?;
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
     #[inline]
     pub fn bind<T: Bindable>(&mut self, value: T) -> Result<()> {
         value.bind(self)?;
@@ -160,6 +337,59 @@ impl<'l> Statement<'l> {
         Ok(())
     }
 
+    /// Advance through all remaining rows, passing each one to a callback.
+    ///
+    /// Unlike `Cursor`, a single row is reused throughout, and columns are read only when
+    /// accessed. The iteration stops at the first error, including one returned by the callback
+    /// and one of reading a column via indexing or iteration, which is returned after the
+    /// callback.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (name TEXT, age INTEGER)")?;
+    /// # connection.execute("INSERT INTO users VALUES ('Alice', 42), ('Bob', 69)")?;
+    /// let mut statement = connection.prepare("SELECT * FROM users")?;
+    /// let mut total = 0;
+    /// statement.for_each_row(|row| {
+    ///     total += row.try_read::<i64, _>("age")?;
+    ///     Ok(())
+    /// })?;
+    /// assert_eq!(total, 42 + 69);
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    #[inline]
+    pub fn for_each_row<F>(&mut self, callback: F) -> Result<()>
+    where
+        F: FnMut(&Row) -> Result<()>,
+    {
+        crate::cursor::for_each_row(self, callback)
+    }
+
+    /// Create a cursor whose rows borrow from the buffers of SQLite.
+    ///
+    /// Unlike `Cursor`, the cursor does not allocate for each row, but it is not an iterator, as
+    /// each row is valid only until the next one is requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (name TEXT); INSERT INTO users VALUES ('Alice')")?;
+    /// let mut statement = connection.prepare("SELECT name FROM users")?;
+    /// let mut rows = statement.rows_ref();
+    /// while let Some(row) = rows.next()? {
+    ///     let name: &str = row.read_ref(0)?.try_into()?;
+    ///     assert_eq!(name, "Alice");
+    /// }
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    #[inline]
+    pub fn rows_ref(&mut self) -> RowsRef<'l, '_> {
+        crate::cursor::new_ref(self)
+    }
+
     /// Create a cursor.
     #[inline]
     pub fn iter(&mut self) -> Cursor<'l, '_> {
@@ -191,6 +421,56 @@ impl<'l> Statement<'l> {
         ReadableWithIndex::read(self, index)
     }
 
+    /// Read a value from a column without copying it.
+    ///
+    /// Strings and binary data are borrowed from the buffers of SQLite and stay valid until the
+    /// next step or reset. In case of integer indices, the first column has index 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite::ValueRef;
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// let mut statement = connection.prepare("SELECT 'Alice', 42")?;
+    /// statement.next()?;
+    /// assert_eq!(statement.read_ref(0)?, ValueRef::Text("Alice"));
+    /// assert_eq!(statement.read_ref(1)?, ValueRef::Integer(42));
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    pub fn read_ref<T: ColumnIndex>(&self, index: T) -> Result<ValueRef<'_>> {
+        let index = index.index(self)? as c_int;
+        unsafe {
+            Ok(match ffi::sqlite3_column_type(self.raw.0, index) {
+                ffi::SQLITE_BLOB => {
+                    let pointer = ffi::sqlite3_column_blob(self.raw.0, index);
+                    let count = ffi::sqlite3_column_bytes(self.raw.0, index) as usize;
+                    if pointer.is_null() {
+                        ValueRef::Blob(&[])
+                    } else {
+                        ValueRef::Blob(std::slice::from_raw_parts(pointer as *const u8, count))
+                    }
+                }
+                ffi::SQLITE_FLOAT => ValueRef::Real(ffi::sqlite3_column_double(self.raw.0, index)),
+                ffi::SQLITE_INTEGER => {
+                    ValueRef::Integer(ffi::sqlite3_column_int64(self.raw.0, index))
+                }
+                ffi::SQLITE_TEXT => {
+                    let pointer = ffi::sqlite3_column_text(self.raw.0, index);
+                    if pointer.is_null() {
+                        raise!("cannot read a text column");
+                    }
+                    let count = ffi::sqlite3_column_bytes(self.raw.0, index) as usize;
+                    let bytes = std::slice::from_raw_parts(pointer, count);
+                    match std::str::from_utf8(bytes) {
+                        Ok(value) => ValueRef::Text(value),
+                        _ => raise!("cannot read a text column (invalid UTF-8)"),
+                    }
+                }
+                _ => ValueRef::Null,
+            })
+        }
+    }
+
     /// Return the number of columns.
     #[inline]
     pub fn column_count(&self) -> usize {
@@ -234,6 +514,36 @@ impl<'l> Statement<'l> {
         )
     }
 
+    /// Return the declared type of a column.
+    ///
+    /// The declared type is the type given in the definition of the table that the column
+    /// originates from, and it is absent for expressions. In case of integer indices, the first
+    /// column has index 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name VARCHAR(42))");
+    /// let statement = connection.prepare("SELECT id, name, id + 1 FROM users")?;
+    /// assert_eq!(statement.column_decltype(0)?, Some("INTEGER"));
+    /// assert_eq!(statement.column_decltype(1)?, Some("VARCHAR(42)"));
+    /// assert_eq!(statement.column_decltype(2)?, None);
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    pub fn column_decltype<T: ColumnIndex>(&self, index: T) -> Result<Option<&str>> {
+        unsafe {
+            let pointer = ffi::sqlite3_column_decltype(self.raw.0, index.index(self)? as c_int);
+            if pointer.is_null() {
+                return Ok(None);
+            }
+            match c_str_to_str!(pointer) {
+                Ok(value) => Ok(Some(value)),
+                _ => raise!("failed to process a declared type"),
+            }
+        }
+    }
+
     /// Return the index for a named parameter if exists.
     ///
     /// # Examples
@@ -264,6 +574,55 @@ impl<'l> Statement<'l> {
         Ok(())
     }
 
+    /// Return the value of a counter.
+    ///
+    /// If `reset` is `true`, the counter is set to zero after being read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sqlite::{State, StatementStatus};
+    /// # let connection = sqlite::open(":memory:").unwrap();
+    /// # connection.execute("CREATE TABLE users (id INTEGER, name STRING)");
+    /// let query = "SELECT * FROM users WHERE name = 'Bob'";
+    /// let mut statement = connection.prepare(query)?;
+    /// assert_eq!(statement.next()?, State::Done);
+    /// assert_eq!(statement.status(StatementStatus::Run, false), 1);
+    /// # Ok::<(), sqlite::Error>(())
+    /// ```
+    pub fn status(&self, counter: StatementStatus, reset: bool) -> usize {
+        let counter = match counter {
+            StatementStatus::FullScanStep => ffi::SQLITE_STMTSTATUS_FULLSCAN_STEP,
+            StatementStatus::Sort => ffi::SQLITE_STMTSTATUS_SORT,
+            StatementStatus::AutoIndex => ffi::SQLITE_STMTSTATUS_AUTOINDEX,
+            StatementStatus::VirtualMachineStep => ffi::SQLITE_STMTSTATUS_VM_STEP,
+            StatementStatus::Reprepare => ffi::SQLITE_STMTSTATUS_REPREPARE,
+            StatementStatus::Run => ffi::SQLITE_STMTSTATUS_RUN,
+            StatementStatus::FilterMiss => ffi::SQLITE_STMTSTATUS_FILTER_MISS,
+            StatementStatus::FilterHit => ffi::SQLITE_STMTSTATUS_FILTER_HIT,
+            StatementStatus::MemoryUsed => ffi::SQLITE_STMTSTATUS_MEMUSED,
+        };
+        unsafe { ffi::sqlite3_stmt_status(self.raw.0, counter, c_int::from(reset)) as usize }
+    }
+
+    /// Check if the statement does not write to the database directly.
+    #[inline]
+    pub fn is_readonly(&self) -> bool {
+        unsafe { ffi::sqlite3_stmt_readonly(self.raw.0) != 0 }
+    }
+
+    /// Check if the statement has been stepped but not run to completion or reset.
+    #[inline]
+    pub fn is_busy(&self) -> bool {
+        unsafe { ffi::sqlite3_stmt_busy(self.raw.0) != 0 }
+    }
+
+    /// Check if the statement is an `EXPLAIN` or `EXPLAIN QUERY PLAN` statement.
+    #[inline]
+    pub fn is_explain(&self) -> bool {
+        unsafe { ffi::sqlite3_stmt_isexplain(self.raw.0) != 0 }
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn as_raw(&self) -> *mut ffi::sqlite3_stmt {
@@ -306,6 +665,28 @@ where
     }
 }
 
+impl<F> Bindable for Parameters<F>
+where
+    F: FnOnce(&mut Statement) -> Result<()>,
+{
+    #[inline]
+    fn bind(self, statement: &mut Statement) -> Result<()> {
+        (self.0)(statement)
+    }
+}
+
+impl<T, const N: usize> Bindable for [T; N]
+where
+    T: BindableWithIndex,
+{
+    fn bind(self, statement: &mut Statement) -> Result<()> {
+        for (index, value) in self.into_iter().enumerate() {
+            value.bind(statement, index + 1)?;
+        }
+        Ok(())
+    }
+}
+
 impl<T> Bindable for &[T]
 where
     T: BindableWithIndex + Clone,
@@ -331,6 +712,37 @@ where
     }
 }
 
+impl<T> ToSql for &T
+where
+    T: ToSql + ?Sized,
+{
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        (**self).to_sql()
+    }
+}
+
+impl<'l> From<ValueRef<'l>> for ToSqlOutput<'l> {
+    #[inline]
+    fn from(value: ValueRef<'l>) -> Self {
+        ToSqlOutput::Borrowed(value)
+    }
+}
+
+impl From<Value> for ToSqlOutput<'_> {
+    #[inline]
+    fn from(value: Value) -> Self {
+        ToSqlOutput::Owned(value)
+    }
+}
+
+impl Bindable for () {
+    #[inline]
+    fn bind(self, _: &mut Statement) -> Result<()> {
+        Ok(())
+    }
+}
+
 impl BindableWithIndex for &[u8] {
     fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
         unsafe {
@@ -399,6 +811,20 @@ impl BindableWithIndex for &str {
     }
 }
 
+impl BindableWithIndex for String {
+    #[inline]
+    fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+        self.as_str().bind(statement, index)
+    }
+}
+
+impl BindableWithIndex for Vec<u8> {
+    #[inline]
+    fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+        BindableWithIndex::bind(self.as_slice(), statement, index)
+    }
+}
+
 impl BindableWithIndex for () {
     fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
         unsafe {
@@ -411,6 +837,86 @@ impl BindableWithIndex for () {
     }
 }
 
+macro_rules! implement(
+    (@integer $($type:ty),+) => {
+        $(
+            impl BindableWithIndex for $type {
+                #[inline]
+                fn bind<T: ParameterIndex>(
+                    self,
+                    statement: &mut Statement,
+                    index: T,
+                ) -> Result<()> {
+                    match i64::try_from(self) {
+                        Ok(value) => value.bind(statement, index),
+                        _ => Err(crate::error::out_of_range(self)),
+                    }
+                }
+            }
+
+            impl ReadableWithIndex for $type {
+                #[inline]
+                fn read<T: ColumnIndex>(statement: &Statement, index: T) -> Result<Self> {
+                    let value = i64::read(statement, index)?;
+                    <$type>::try_from(value).map_err(|_| crate::error::out_of_range(value))
+                }
+            }
+        )+
+    };
+    (@string $($type:ty),+) => {
+        $(
+            impl BindableWithIndex for $type {
+                #[inline]
+                fn bind<T: ParameterIndex>(
+                    self,
+                    statement: &mut Statement,
+                    index: T,
+                ) -> Result<()> {
+                    (&*self).bind(statement, index)
+                }
+            }
+
+            impl ReadableWithIndex for $type {
+                #[inline]
+                fn read<T: ColumnIndex>(statement: &Statement, index: T) -> Result<Self> {
+                    String::read(statement, index).map(Into::into)
+                }
+            }
+        )+
+    };
+);
+
+implement!(@integer i32, i16, i8, u64, u32, u16, u8, isize, usize);
+implement!(@string Box<str>, Cow<'_, str>, Rc<str>, Arc<str>);
+
+impl BindableWithIndex for bool {
+    #[inline]
+    fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+        i64::from(self).bind(statement, index)
+    }
+}
+
+impl BindableWithIndex for f32 {
+    #[inline]
+    fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+        f64::from(self).bind(statement, index)
+    }
+}
+
+impl BindableWithIndex for char {
+    #[inline]
+    fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+        (&*self.encode_utf8(&mut [0; 4])).bind(statement, index)
+    }
+}
+
+impl<const N: usize> BindableWithIndex for [u8; N] {
+    #[inline]
+    fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+        BindableWithIndex::bind(&self[..], statement, index)
+    }
+}
+
 impl BindableWithIndex for Value {
     #[inline]
     fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
@@ -421,15 +927,47 @@ impl BindableWithIndex for Value {
 impl BindableWithIndex for &Value {
     fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
         match self {
-            Value::Binary(ref value) => (value as &[u8]).bind(statement, index),
+            Value::Binary(ref value) => BindableWithIndex::bind(value.as_slice(), statement, index),
             Value::Float(value) => value.bind(statement, index),
             Value::Integer(value) => value.bind(statement, index),
             Value::String(ref value) => (value as &str).bind(statement, index),
-            Value::Null => ().bind(statement, index),
+            Value::Null => BindableWithIndex::bind((), statement, index),
         }
     }
 }
 
+impl BindableWithIndex for ValueRef<'_> {
+    fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+        match self {
+            ValueRef::Blob(value) => BindableWithIndex::bind(value, statement, index),
+            ValueRef::Real(value) => value.bind(statement, index),
+            ValueRef::Integer(value) => value.bind(statement, index),
+            ValueRef::Text(value) => value.bind(statement, index),
+            ValueRef::Null => BindableWithIndex::bind((), statement, index),
+        }
+    }
+}
+
+impl BindableWithIndex for ToSqlOutput<'_> {
+    #[inline]
+    fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+        match self {
+            ToSqlOutput::Borrowed(value) => value.bind(statement, index),
+            ToSqlOutput::Owned(value) => value.bind(statement, index),
+        }
+    }
+}
+
+impl<T> BindableWithIndex for T
+where
+    T: ToSql,
+{
+    #[inline]
+    fn bind<U: ParameterIndex>(self, statement: &mut Statement, index: U) -> Result<()> {
+        self.to_sql()?.bind(statement, index)
+    }
+}
+
 impl<T> BindableWithIndex for Option<T>
 where
     T: BindableWithIndex,
@@ -438,7 +976,7 @@ where
     fn bind<U: ParameterIndex>(self, statement: &mut Statement, index: U) -> Result<()> {
         match self {
             Some(value) => value.bind(statement, index),
-            None => ().bind(statement, index),
+            None => BindableWithIndex::bind((), statement, index),
         }
     }
 }
@@ -451,7 +989,7 @@ where
     fn bind<U: ParameterIndex>(self, statement: &mut Statement, index: U) -> Result<()> {
         match self {
             Some(value) => value.clone().bind(statement, index),
-            None => ().bind(statement, index),
+            None => BindableWithIndex::bind((), statement, index),
         }
     }
 }
@@ -538,6 +1076,11 @@ impl ReadableWithIndex for i64 {
 
 impl ReadableWithIndex for String {
     fn read<T: ColumnIndex>(statement: &Statement, index: T) -> Result<Self> {
+        // Reading binary data as text would invalidate previously borrowed values.
+        if statement.column_type(index)? == Type::Binary {
+            let value = Vec::<u8>::read(statement, index)?;
+            return Ok(String::from_utf8_lossy(&value).into_owned());
+        }
         unsafe {
             let pointer =
                 ffi::sqlite3_column_text(statement.raw.0, index.index(statement)? as c_int);
@@ -549,6 +1092,45 @@ impl ReadableWithIndex for String {
     }
 }
 
+impl ReadableWithIndex for bool {
+    #[inline]
+    fn read<T: ColumnIndex>(statement: &Statement, index: T) -> Result<Self> {
+        Ok(i64::read(statement, index)? != 0)
+    }
+}
+
+impl ReadableWithIndex for f32 {
+    #[inline]
+    fn read<T: ColumnIndex>(statement: &Statement, index: T) -> Result<Self> {
+        let value = f64::read(statement, index)?;
+        if value.is_finite() && value.abs() > f32::MAX as f64 {
+            return Err(crate::error::out_of_range(value));
+        }
+        Ok(value as f32)
+    }
+}
+
+impl ReadableWithIndex for char {
+    fn read<T: ColumnIndex>(statement: &Statement, index: T) -> Result<Self> {
+        let value = String::read(statement, index)?;
+        let mut characters = value.chars();
+        match (characters.next(), characters.next()) {
+            (Some(character), None) => Ok(character),
+            _ => raise!("expected a single character ({value})"),
+        }
+    }
+}
+
+impl<const N: usize> ReadableWithIndex for [u8; N] {
+    fn read<T: ColumnIndex>(statement: &Statement, index: T) -> Result<Self> {
+        let value = Vec::<u8>::read(statement, index)?;
+        match value.try_into() {
+            Ok(value) => Ok(value),
+            Err(value) => raise!("expected {N} bytes but found {}", value.len()),
+        }
+    }
+}
+
 impl ReadableWithIndex for Value {
     fn read<T: ColumnIndex>(statement: &Statement, index: T) -> Result<Self> {
         Ok(match statement.column_type(index)? {
@@ -561,6 +1143,13 @@ impl ReadableWithIndex for Value {
     }
 }
 
+impl<T: FromSql> ReadableWithIndex for T {
+    #[inline]
+    fn read<U: ColumnIndex>(statement: &Statement, index: U) -> Result<Self> {
+        T::from_sql(statement.read_ref(index)?)
+    }
+}
+
 impl<T: ReadableWithIndex> ReadableWithIndex for Option<T> {
     fn read<U: ColumnIndex>(statement: &Statement, index: U) -> Result<Self> {
         if statement.column_type(index)? == Type::Null {
@@ -571,6 +1160,60 @@ impl<T: ReadableWithIndex> ReadableWithIndex for Option<T> {
     }
 }
 
+macro_rules! implement(
+    (@from-row $($type:ident)+) => {
+        impl<$($type),+> FromRow for ($($type,)+)
+        where
+            $($type: ReadableWithIndex,)+
+        {
+            #[allow(unused_assignments)]
+            fn from_row(statement: &Statement) -> Result<Self> {
+                let mut index = 0;
+                Ok(($({
+                    index += 1;
+                    statement.read::<$type, _>(index - 1)?
+                },)+))
+            }
+        }
+    };
+    ($($type:ident)+) => {
+        implement!(@from-row $($type)+);
+
+        impl<$($type),+> Bindable for ($($type,)+)
+        where
+            $($type: BindableWithIndex,)+
+        {
+            #[allow(non_snake_case, unused_assignments)]
+            fn bind(self, statement: &mut Statement) -> Result<()> {
+                let ($($type,)+) = self;
+                let mut index = 0;
+                $(
+                    index += 1;
+                    BindableWithIndex::bind($type, statement, index)?;
+                )+
+                Ok(())
+            }
+        }
+    };
+);
+
+implement!(A);
+implement!(@from-row A B);
+implement!(A B C);
+implement!(A B C D);
+implement!(A B C D E);
+implement!(A B C D E F);
+implement!(A B C D E F G);
+implement!(A B C D E F G H);
+implement!(A B C D E F G H I);
+implement!(A B C D E F G H I J);
+implement!(A B C D E F G H I J K);
+implement!(A B C D E F G H I J K L);
+implement!(A B C D E F G H I J K L M);
+implement!(A B C D E F G H I J K L M N);
+implement!(A B C D E F G H I J K L M N O);
+implement!(A B C D E F G H I J K L M N O P);
+
 pub fn new<'l, T>(raw_connection: *mut ffi::sqlite3, statement: T) -> Result<Statement<'l>>
 where
     T: AsRef<str>,
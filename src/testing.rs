@@ -0,0 +1,528 @@
+use core::ffi::c_int;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::sync::{Arc, Mutex};
+
+use crate::error::{error, Result};
+use crate::vfs::{lock, AccessCheck, FileOptions, LockLevel, Vfs, VfsFile};
+
+/// A virtual file system injecting faults into the default one.
+///
+/// The virtual file system is controlled via a `FaultInjector`, which can fail writes and syncs,
+/// count them, and simulate a power loss, in which case all data not synced to persistent
+/// storage is lost, and connections opened before are to be closed.
+///
+/// Shared memory is not supported, and therefore the write-ahead logging mode is available only
+/// with the exclusive locking mode.
+///
+/// # Examples
+///
+/// ```
+/// use sqlite::{Connection, FaultVfs, OpenFlags, WriteFault};
+/// # let folder = temporary::Folder::new("sqlite").unwrap();
+/// # let path = folder.path().join("database.sqlite3");
+///
+/// let vfs = FaultVfs::new()?;
+/// let injector = vfs.injector();
+/// sqlite::register_vfs("fault", vfs, false)?;
+///
+/// let flags = OpenFlags::new().with_create().with_read_write();
+/// let connection = Connection::open_with_flags_and_vfs(&path, flags, "fault")?;
+/// connection.execute("CREATE TABLE users (name TEXT)")?;
+///
+/// injector.fail_write(1, WriteFault::Full);
+/// let error = connection.execute("INSERT INTO users VALUES ('Alice')").unwrap_err();
+/// assert_eq!(error.code, Some(sqlite::ffi::SQLITE_FULL as isize));
+/// # Ok::<(), sqlite::Error>(())
+/// ```
+pub struct FaultVfs {
+    base: Base,
+    state: Arc<Mutex<State>>,
+}
+
+/// A controller of a `FaultVfs`.
+#[derive(Clone)]
+pub struct FaultInjector {
+    base: Base,
+    state: Arc<Mutex<State>>,
+}
+
+/// A fault of a write.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WriteFault {
+    /// Fail with a generic I/O error (`EIO`).
+    Io,
+    /// Fail due to a lack of space (`ENOSPC`).
+    Full,
+    /// Succeed but retain only the given number of leading bytes in case of a power loss before
+    /// the data is synced.
+    Torn(usize),
+}
+
+/// A fault of a sync.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncFault {
+    /// Fail with a generic I/O error (`EIO`).
+    Io,
+    /// Succeed without syncing anything.
+    Lost,
+}
+
+/// A file of a `FaultVfs`.
+pub struct FaultFile {
+    file: BaseFile,
+    path: Option<String>,
+    generation: usize,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    generation: usize,
+    writes: usize,
+    syncs: usize,
+    write_faults: HashMap<usize, WriteFault>,
+    sync_faults: HashMap<usize, SyncFault>,
+    pending: HashMap<String, Pending>,
+}
+
+// The changes to a file since the last sync.
+struct Pending {
+    size: u64,
+    undo: Vec<Undo>,
+}
+
+struct Undo {
+    offset: u64,
+    data: Vec<u8>,
+    kept: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Base(*mut ffi::sqlite3_vfs);
+
+struct BaseFile {
+    raw: Box<[u64]>,
+    _path: Option<CString>,
+}
+
+impl FaultVfs {
+    /// Create a virtual file system on top of the default one.
+    pub fn new() -> Result<Self> {
+        let base = unsafe { ffi::sqlite3_vfs_find(std::ptr::null()) };
+        if base.is_null() {
+            raise!("failed to find the default virtual file system");
+        }
+        Ok(Self {
+            base: Base(base),
+            state: Default::default(),
+        })
+    }
+
+    /// Return the controller.
+    #[inline]
+    pub fn injector(&self) -> FaultInjector {
+        FaultInjector {
+            base: self.base,
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl Vfs for FaultVfs {
+    type File = FaultFile;
+
+    fn open(&self, path: Option<&str>, options: FileOptions) -> Result<FaultFile> {
+        // The path is not followed by URI parameters as expected by the default file system.
+        let flags = options.0 & !ffi::SQLITE_OPEN_URI;
+        let file = BaseFile::open(self.base, path, flags)?;
+        Ok(FaultFile {
+            file,
+            path: path
+                .filter(|_| !options.is_delete_on_close())
+                .map(Into::into),
+            generation: lock(&self.state).generation,
+            state: self.state.clone(),
+        })
+    }
+
+    fn delete(&self, path: &str, sync_directory: bool) -> Result<()> {
+        let name = str_to_cstr!(path);
+        let base = self.base.0;
+        unsafe {
+            let delete = (*base).xDelete.unwrap();
+            check(delete(base, name.as_ptr(), c_int::from(sync_directory)))?;
+        }
+        lock(&self.state).pending.remove(path);
+        Ok(())
+    }
+
+    fn access(&self, path: &str, check: AccessCheck) -> Result<bool> {
+        let name = str_to_cstr!(path);
+        let flags = match check {
+            AccessCheck::Exists => ffi::SQLITE_ACCESS_EXISTS,
+            AccessCheck::ReadWrite => ffi::SQLITE_ACCESS_READWRITE,
+            AccessCheck::Read => ffi::SQLITE_ACCESS_READ,
+        };
+        let base = self.base.0;
+        let mut output = 0;
+        unsafe {
+            let access = (*base).xAccess.unwrap();
+            self::check(access(base, name.as_ptr(), flags, &mut output))?;
+        }
+        Ok(output != 0)
+    }
+
+    fn full_pathname(&self, path: &str) -> Result<String> {
+        let name = str_to_cstr!(path);
+        let base = self.base.0;
+        unsafe {
+            let mut output = vec![0u8; (*base).mxPathname.max(0) as usize + 1];
+            let full_pathname = (*base).xFullPathname.unwrap();
+            check(full_pathname(
+                base,
+                name.as_ptr(),
+                output.len() as c_int,
+                output.as_mut_ptr() as *mut _,
+            ))?;
+            Ok(c_str_to_string!(output.as_ptr()))
+        }
+    }
+
+    fn randomness(&self, buffer: &mut [u8]) {
+        let base = self.base.0;
+        unsafe {
+            let randomness = (*base).xRandomness.unwrap();
+            randomness(base, buffer.len() as c_int, buffer.as_mut_ptr() as *mut _);
+        }
+    }
+}
+
+impl FaultInjector {
+    /// Make the nth write from now fail, starting from one.
+    pub fn fail_write(&self, nth: usize, fault: WriteFault) {
+        let mut state = lock(&self.state);
+        let index = state.writes + nth.max(1);
+        state.write_faults.insert(index, fault);
+    }
+
+    /// Make the nth sync from now fail, starting from one.
+    pub fn fail_sync(&self, nth: usize, fault: SyncFault) {
+        let mut state = lock(&self.state);
+        let index = state.syncs + nth.max(1);
+        state.sync_faults.insert(index, fault);
+    }
+
+    /// Cancel all scheduled faults.
+    pub fn clear(&self) {
+        let mut state = lock(&self.state);
+        state.write_faults.clear();
+        state.sync_faults.clear();
+    }
+
+    /// Return the number of writes so far.
+    #[inline]
+    pub fn writes(&self) -> usize {
+        lock(&self.state).writes
+    }
+
+    /// Return the number of syncs so far.
+    #[inline]
+    pub fn syncs(&self) -> usize {
+        lock(&self.state).syncs
+    }
+
+    /// Simulate a power loss.
+    ///
+    /// All data not synced to persistent storage is discarded. Files opened before fail all
+    /// subsequent operations, and the corresponding connections are to be closed.
+    pub fn power_loss(&self) -> Result<()> {
+        let mut state = lock(&self.state);
+        state.generation += 1;
+        let flags = ffi::SQLITE_OPEN_READWRITE | ffi::SQLITE_OPEN_MAIN_DB;
+        for (path, pending) in state.pending.drain() {
+            let mut file = match BaseFile::open(self.base, Some(&path), flags) {
+                Ok(file) => file,
+                _ => continue,
+            };
+            let mut size = pending.size;
+            for undo in pending.undo.iter().rev() {
+                if undo.kept > 0 {
+                    size = size.max(undo.offset + undo.kept as u64);
+                }
+                if undo.data.len() > undo.kept {
+                    file.write(&undo.data[undo.kept..], undo.offset + undo.kept as u64)?;
+                }
+            }
+            file.truncate(size)?;
+            file.sync(false)?;
+        }
+        Ok(())
+    }
+}
+
+impl FaultFile {
+    fn ensure_current(&self, state: &State, code: c_int) -> Result<()> {
+        if state.generation != self.generation {
+            return Err(error(code));
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, state: &mut State, offset: u64, size: usize, kept: usize) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let current = self.file.file_size()?;
+        let mut data = vec![0; current.saturating_sub(offset).min(size as u64) as usize];
+        self.file.read(&mut data, offset)?;
+        state
+            .pending
+            .entry(path.clone())
+            .or_insert_with(|| Pending {
+                size: current,
+                undo: Vec::new(),
+            })
+            .undo
+            .push(Undo { offset, data, kept });
+        Ok(())
+    }
+}
+
+impl VfsFile for FaultFile {
+    fn read(&mut self, buffer: &mut [u8], offset: u64) -> Result<usize> {
+        self.ensure_current(&lock(&self.state), ffi::SQLITE_IOERR_READ)?;
+        self.file.read(buffer, offset)
+    }
+
+    fn write(&mut self, data: &[u8], offset: u64) -> Result<()> {
+        let state = self.state.clone();
+        let mut state = lock(&state);
+        self.ensure_current(&state, ffi::SQLITE_IOERR_WRITE)?;
+        state.writes += 1;
+        let index = state.writes;
+        let kept = match state.write_faults.remove(&index) {
+            Some(WriteFault::Io) => return Err(error(ffi::SQLITE_IOERR_WRITE)),
+            Some(WriteFault::Full) => return Err(error(ffi::SQLITE_FULL)),
+            Some(WriteFault::Torn(kept)) => kept.min(data.len()),
+            None => 0,
+        };
+        self.record(&mut state, offset, data.len(), kept)?;
+        self.file.write(data, offset)
+    }
+
+    fn truncate(&mut self, size: u64) -> Result<()> {
+        let state = self.state.clone();
+        let mut state = lock(&state);
+        self.ensure_current(&state, ffi::SQLITE_IOERR_TRUNCATE)?;
+        let current = self.file.file_size()?;
+        if current > size {
+            self.record(&mut state, size, (current - size) as usize, 0)?;
+        }
+        self.file.truncate(size)
+    }
+
+    fn sync(&mut self, data_only: bool) -> Result<()> {
+        let state = self.state.clone();
+        let mut state = lock(&state);
+        self.ensure_current(&state, ffi::SQLITE_IOERR_FSYNC)?;
+        state.syncs += 1;
+        let index = state.syncs;
+        match state.sync_faults.remove(&index) {
+            Some(SyncFault::Io) => return Err(error(ffi::SQLITE_IOERR_FSYNC)),
+            Some(SyncFault::Lost) => return Ok(()),
+            None => {}
+        }
+        self.file.sync(data_only)?;
+        if let Some(path) = &self.path {
+            state.pending.remove(path);
+        }
+        Ok(())
+    }
+
+    fn file_size(&self) -> Result<u64> {
+        self.ensure_current(&lock(&self.state), ffi::SQLITE_IOERR_FSTAT)?;
+        self.file.file_size()
+    }
+
+    fn lock(&mut self, level: LockLevel) -> Result<()> {
+        self.ensure_current(&lock(&self.state), ffi::SQLITE_IOERR_LOCK)?;
+        self.file.lock(level)
+    }
+
+    #[inline]
+    fn unlock(&mut self, level: LockLevel) -> Result<()> {
+        self.file.unlock(level)
+    }
+
+    #[inline]
+    fn check_reserved_lock(&self) -> Result<bool> {
+        self.file.check_reserved_lock()
+    }
+
+    #[inline]
+    fn sector_size(&self) -> usize {
+        self.file.sector_size()
+    }
+}
+
+impl BaseFile {
+    fn open(base: Base, path: Option<&str>, flags: c_int) -> Result<Self> {
+        let path = match path {
+            Some(path) => Some(str_to_cstr!(path)),
+            _ => None,
+        };
+        let base = base.0;
+        unsafe {
+            let size = ((*base).szOsFile.max(0) as usize).div_ceil(8);
+            let mut file = BaseFile {
+                raw: vec![0; size.max(1)].into_boxed_slice(),
+                _path: None,
+            };
+            let open = (*base).xOpen.unwrap();
+            check(open(
+                base,
+                path.as_ref().map_or(std::ptr::null(), |path| path.as_ptr()),
+                file.as_raw(),
+                flags,
+                std::ptr::null_mut(),
+            ))?;
+            file._path = path;
+            Ok(file)
+        }
+    }
+
+    fn read(&mut self, buffer: &mut [u8], offset: u64) -> Result<usize> {
+        unsafe {
+            let read = (*self.methods()).xRead.unwrap();
+            let code = read(
+                self.as_raw(),
+                buffer.as_mut_ptr() as *mut _,
+                buffer.len() as c_int,
+                offset as ffi::sqlite3_int64,
+            );
+            if code == ffi::SQLITE_IOERR_SHORT_READ {
+                let size = self.file_size()?;
+                return Ok(size.saturating_sub(offset).min(buffer.len() as u64) as usize);
+            }
+            check(code)?;
+        }
+        Ok(buffer.len())
+    }
+
+    fn write(&mut self, data: &[u8], offset: u64) -> Result<()> {
+        unsafe {
+            let write = (*self.methods()).xWrite.unwrap();
+            check(write(
+                self.as_raw(),
+                data.as_ptr() as *const _,
+                data.len() as c_int,
+                offset as ffi::sqlite3_int64,
+            ))
+        }
+    }
+
+    fn truncate(&mut self, size: u64) -> Result<()> {
+        unsafe {
+            let truncate = (*self.methods()).xTruncate.unwrap();
+            check(truncate(self.as_raw(), size as ffi::sqlite3_int64))
+        }
+    }
+
+    fn sync(&mut self, data_only: bool) -> Result<()> {
+        let mut flags = ffi::SQLITE_SYNC_NORMAL;
+        if data_only {
+            flags |= ffi::SQLITE_SYNC_DATAONLY;
+        }
+        unsafe {
+            let sync = (*self.methods()).xSync.unwrap();
+            check(sync(self.as_raw(), flags))
+        }
+    }
+
+    fn file_size(&self) -> Result<u64> {
+        let mut size = 0;
+        unsafe {
+            let file_size = (*self.methods()).xFileSize.unwrap();
+            check(file_size(self.as_raw(), &mut size))?;
+        }
+        Ok(size as u64)
+    }
+
+    fn lock(&mut self, level: LockLevel) -> Result<()> {
+        unsafe {
+            let lock = (*self.methods()).xLock.unwrap();
+            check(lock(self.as_raw(), level_to_raw(level)))
+        }
+    }
+
+    fn unlock(&mut self, level: LockLevel) -> Result<()> {
+        unsafe {
+            let unlock = (*self.methods()).xUnlock.unwrap();
+            check(unlock(self.as_raw(), level_to_raw(level)))
+        }
+    }
+
+    fn check_reserved_lock(&self) -> Result<bool> {
+        let mut output = 0;
+        unsafe {
+            let check_reserved_lock = (*self.methods()).xCheckReservedLock.unwrap();
+            check(check_reserved_lock(self.as_raw(), &mut output))?;
+        }
+        Ok(output != 0)
+    }
+
+    fn sector_size(&self) -> usize {
+        unsafe {
+            match (*self.methods()).xSectorSize {
+                Some(sector_size) => sector_size(self.as_raw()).max(0) as usize,
+                _ => 4096,
+            }
+        }
+    }
+
+    #[inline]
+    fn as_raw(&self) -> *mut ffi::sqlite3_file {
+        self.raw.as_ptr() as *mut _
+    }
+
+    #[inline]
+    fn methods(&self) -> *const ffi::sqlite3_io_methods {
+        unsafe { (*self.as_raw()).pMethods }
+    }
+}
+
+impl Drop for BaseFile {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let methods = self.methods();
+            if let Some(close) = methods.as_ref().and_then(|methods| methods.xClose) {
+                close(self.as_raw());
+            }
+        }
+    }
+}
+
+unsafe impl Send for Base {}
+
+unsafe impl Sync for Base {}
+
+unsafe impl Send for BaseFile {}
+
+fn check(code: c_int) -> Result<()> {
+    match code {
+        ffi::SQLITE_OK => Ok(()),
+        code => Err(error(code)),
+    }
+}
+
+fn level_to_raw(level: LockLevel) -> c_int {
+    match level {
+        LockLevel::None => ffi::SQLITE_LOCK_NONE,
+        LockLevel::Shared => ffi::SQLITE_LOCK_SHARED,
+        LockLevel::Reserved => ffi::SQLITE_LOCK_RESERVED,
+        LockLevel::Pending => ffi::SQLITE_LOCK_PENDING,
+        LockLevel::Exclusive => ffi::SQLITE_LOCK_EXCLUSIVE,
+    }
+}
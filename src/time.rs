@@ -0,0 +1,55 @@
+use ::time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::datetime::{format_date, format_offset, format_time, implement, Timestamp};
+use crate::error::{Error, Result};
+
+implement!(
+    OffsetDateTime,
+    |value| {
+        format_date_time(value.date(), value.time())
+            .map(|string| string + &format_offset(value.offset().whole_seconds()))
+    },
+    |timestamp| {
+        UtcOffset::from_whole_seconds(timestamp.offset)
+            .ok()
+            .and_then(|offset| Some(utc(timestamp)?.to_offset(offset)))
+    }
+);
+implement!(
+    PrimitiveDateTime,
+    |value| format_date_time(value.date(), value.time()),
+    |timestamp| utc(timestamp).map(|value| PrimitiveDateTime::new(value.date(), value.time()))
+);
+implement!(
+    Date,
+    |value| format_date(value.year(), value.month() as u32, value.day() as u32),
+    |timestamp| utc(timestamp).map(|value| value.date())
+);
+implement!(
+    Time,
+    |value| Ok::<_, Error>(format_time(
+        value.hour() as u32,
+        value.minute() as u32,
+        value.second() as u32,
+        value.nanosecond()
+    )),
+    |timestamp| utc(timestamp).map(|value| value.time())
+);
+
+fn format_date_time(date: Date, time: Time) -> Result<String> {
+    Ok(format!(
+        "{} {}",
+        format_date(date.year(), date.month() as u32, date.day() as u32)?,
+        format_time(
+            time.hour() as u32,
+            time.minute() as u32,
+            time.second() as u32,
+            time.nanosecond()
+        ),
+    ))
+}
+
+fn utc(timestamp: Timestamp) -> Option<OffsetDateTime> {
+    let nanoseconds = timestamp.seconds as i128 * 1_000_000_000 + timestamp.nanoseconds as i128;
+    OffsetDateTime::from_unix_timestamp_nanos(nanoseconds).ok()
+}
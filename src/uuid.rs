@@ -0,0 +1,91 @@
+use ::uuid::fmt::Hyphenated;
+use ::uuid::Uuid;
+
+use crate::error::{Error, Result};
+use crate::statement::{
+    BindableWithIndex, ColumnIndex, ParameterIndex, ReadableWithIndex, Statement,
+};
+use crate::value::Value;
+
+impl BindableWithIndex for Uuid {
+    #[inline]
+    fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+        BindableWithIndex::bind(&self.as_bytes()[..], statement, index)
+    }
+}
+
+impl BindableWithIndex for Hyphenated {
+    #[inline]
+    fn bind<T: ParameterIndex>(self, statement: &mut Statement, index: T) -> Result<()> {
+        self.encode_lower(&mut Uuid::encode_buffer())
+            .bind(statement, index)
+    }
+}
+
+impl From<Uuid> for Value {
+    #[inline]
+    fn from(value: Uuid) -> Self {
+        Value::Binary(value.as_bytes().to_vec())
+    }
+}
+
+impl From<Hyphenated> for Value {
+    #[inline]
+    fn from(value: Hyphenated) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+macro_rules! implement(
+    ($type:ty) => {
+        impl ReadableWithIndex for $type {
+            #[inline]
+            fn read<T: ColumnIndex>(statement: &Statement, index: T) -> Result<Self> {
+                <$type>::try_from(&Value::read(statement, index)?)
+            }
+        }
+
+        impl TryFrom<&Value> for Option<$type> {
+            type Error = Error;
+
+            #[inline]
+            fn try_from(value: &Value) -> Result<Self> {
+                if let Value::Null = value {
+                    return Ok(None);
+                }
+                <$type>::try_from(value).map(Some)
+            }
+        }
+    };
+);
+
+implement!(Uuid);
+implement!(Hyphenated);
+
+impl TryFrom<&Value> for Uuid {
+    type Error = Error;
+
+    /// Convert a 16-byte blob or a string in any of the formats supported by `Uuid::parse_str`.
+    fn try_from(value: &Value) -> Result<Self> {
+        match value {
+            Value::Binary(ref value) => match Uuid::from_slice(value) {
+                Ok(value) => Ok(value),
+                _ => raise!("expected 16 bytes but found {}", value.len()),
+            },
+            Value::String(ref value) => match Uuid::parse_str(value) {
+                Ok(value) => Ok(value),
+                Err(error) => raise!("failed to parse a UUID ({error})"),
+            },
+            _ => raise!("failed to convert"),
+        }
+    }
+}
+
+impl TryFrom<&Value> for Hyphenated {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &Value) -> Result<Self> {
+        Uuid::try_from(value).map(Uuid::hyphenated)
+    }
+}
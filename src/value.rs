@@ -1,4 +1,7 @@
+use std::borrow::Cow;
 use std::convert::TryFrom;
+use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::error::{Error, Result};
 
@@ -18,6 +21,21 @@ pub enum Value {
     Null,
 }
 
+/// A value borrowed from a prepared statement or from a `Value`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValueRef<'l> {
+    /// Binary data.
+    Blob(&'l [u8]),
+    /// An integer number.
+    Integer(i64),
+    /// A floating-point number.
+    Real(f64),
+    /// A string.
+    Text(&'l str),
+    /// A null value.
+    Null,
+}
+
 /// The type of a value.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Type {
@@ -55,6 +73,43 @@ impl Value {
     }
 }
 
+impl ValueRef<'_> {
+    /// Return the type.
+    pub fn kind(&self) -> Type {
+        match self {
+            ValueRef::Blob(_) => Type::Binary,
+            ValueRef::Real(_) => Type::Float,
+            ValueRef::Integer(_) => Type::Integer,
+            ValueRef::Text(_) => Type::String,
+            ValueRef::Null => Type::Null,
+        }
+    }
+}
+
+impl<'l> From<&'l Value> for ValueRef<'l> {
+    fn from(value: &'l Value) -> Self {
+        match value {
+            Value::Binary(ref value) => ValueRef::Blob(value),
+            &Value::Float(value) => ValueRef::Real(value),
+            &Value::Integer(value) => ValueRef::Integer(value),
+            Value::String(ref value) => ValueRef::Text(value),
+            Value::Null => ValueRef::Null,
+        }
+    }
+}
+
+impl From<ValueRef<'_>> for Value {
+    fn from(value: ValueRef<'_>) -> Self {
+        match value {
+            ValueRef::Blob(value) => Value::Binary(value.to_vec()),
+            ValueRef::Real(value) => Value::Float(value),
+            ValueRef::Integer(value) => Value::Integer(value),
+            ValueRef::Text(value) => Value::String(value.to_string()),
+            ValueRef::Null => Value::Null,
+        }
+    }
+}
+
 macro_rules! implement(
     ($type:ty, Null) => {
         impl From<$type> for Value {
@@ -77,11 +132,69 @@ macro_rules! implement(
 implement!(Vec<u8>, Binary);
 implement!(&[u8], Binary);
 implement!(f64, Float);
+implement!(f32, Float);
 implement!(i64, Integer);
+implement!(i32, Integer);
+implement!(i16, Integer);
+implement!(i8, Integer);
+implement!(u32, Integer);
+implement!(u16, Integer);
+implement!(u8, Integer);
+implement!(bool, Integer);
 implement!(String, String);
 implement!(&str, String);
+implement!(Box<str>, String);
+implement!(Cow<'_, str>, String);
 implement!((), Null);
 
+impl From<Rc<str>> for Value {
+    #[inline]
+    fn from(value: Rc<str>) -> Self {
+        Value::String(value.as_ref().into())
+    }
+}
+
+impl From<Arc<str>> for Value {
+    #[inline]
+    fn from(value: Arc<str>) -> Self {
+        Value::String(value.as_ref().into())
+    }
+}
+
+impl From<char> for Value {
+    #[inline]
+    fn from(value: char) -> Self {
+        Value::String(value.into())
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for Value {
+    #[inline]
+    fn from(value: [u8; N]) -> Self {
+        Value::Binary(value.into())
+    }
+}
+
+macro_rules! implement(
+    ($($type:ty),+) => {
+        $(
+            impl TryFrom<$type> for Value {
+                type Error = Error;
+
+                #[inline]
+                fn try_from(value: $type) -> Result<Self> {
+                    match i64::try_from(value) {
+                        Ok(value) => Ok(Value::Integer(value)),
+                        _ => Err(crate::error::out_of_range(value)),
+                    }
+                }
+            }
+        )+
+    };
+);
+
+implement!(u64, isize, usize);
+
 macro_rules! implement(
     (@value $type:ty, $value:ident) => {
         impl TryFrom<Value> for $type {
@@ -181,6 +294,156 @@ implement!(@value String, String);
 implement!(@reference-lifetime &'l str, String);
 implement!(@reference (), Null);
 
+macro_rules! implement(
+    (@option $type:ty) => {
+        impl<'l> TryFrom<&'l Value> for Option<$type> {
+            type Error = Error;
+
+            #[inline]
+            fn try_from(value: &'l Value) -> Result<Self> {
+                if let Value::Null = value {
+                    return Ok(None);
+                }
+                <$type>::try_from(value).and_then(|value| Ok(Some(value)))
+            }
+        }
+    };
+    (@integer $($type:ty),+) => {
+        $(
+            impl TryFrom<&Value> for $type {
+                type Error = Error;
+
+                #[inline]
+                fn try_from(value: &Value) -> Result<Self> {
+                    if let &Value::Integer(value) = value {
+                        return <$type>::try_from(value)
+                            .map_err(|_| crate::error::out_of_range(value));
+                    }
+                    raise!("failed to convert");
+                }
+            }
+
+            implement!(@option $type);
+        )+
+    };
+    (@string $($type:ty),+) => {
+        $(
+            impl<'l> TryFrom<&'l Value> for $type {
+                type Error = Error;
+
+                #[inline]
+                fn try_from(value: &'l Value) -> Result<Self> {
+                    if let &Value::String(ref value) = value {
+                        return Ok(value.as_str().into());
+                    }
+                    raise!("failed to convert");
+                }
+            }
+
+            implement!(@option $type);
+        )+
+    };
+);
+
+implement!(@integer i32, i16, i8, u64, u32, u16, u8, isize, usize);
+implement!(@string Box<str>, Cow<'l, str>, Rc<str>, Arc<str>);
+
+impl TryFrom<&Value> for bool {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &Value) -> Result<Self> {
+        if let &Value::Integer(value) = value {
+            return Ok(value != 0);
+        }
+        raise!("failed to convert");
+    }
+}
+
+implement!(@option bool);
+
+impl TryFrom<&Value> for f32 {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &Value) -> Result<Self> {
+        if let &Value::Float(value) = value {
+            if value.is_finite() && value.abs() > f32::MAX as f64 {
+                return Err(crate::error::out_of_range(value));
+            }
+            return Ok(value as f32);
+        }
+        raise!("failed to convert");
+    }
+}
+
+implement!(@option f32);
+
+impl TryFrom<&Value> for char {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self> {
+        if let Value::String(ref value) = value {
+            let mut characters = value.chars();
+            if let (Some(character), None) = (characters.next(), characters.next()) {
+                return Ok(character);
+            }
+        }
+        raise!("failed to convert");
+    }
+}
+
+implement!(@option char);
+
+impl<const N: usize> TryFrom<&Value> for [u8; N] {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &Value) -> Result<Self> {
+        if let Value::Binary(ref value) = value {
+            if let Ok(value) = value.as_slice().try_into() {
+                return Ok(value);
+            }
+        }
+        raise!("failed to convert");
+    }
+}
+
+impl<const N: usize> TryFrom<&Value> for Option<[u8; N]> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &Value) -> Result<Self> {
+        if let Value::Null = value {
+            return Ok(None);
+        }
+        <[u8; N]>::try_from(value).map(Some)
+    }
+}
+
+macro_rules! implement(
+    ($type:ty, $value:ident) => {
+        impl<'l> TryFrom<ValueRef<'l>> for $type {
+            type Error = Error;
+
+            #[inline]
+            fn try_from(value: ValueRef<'l>) -> Result<Self> {
+                if let ValueRef::$value(value) = value {
+                    return Ok(value.into());
+                }
+                raise!("failed to convert");
+            }
+        }
+    };
+);
+
+implement!(&'l [u8], Blob);
+implement!(Vec<u8>, Blob);
+implement!(f64, Real);
+implement!(i64, Integer);
+implement!(&'l str, Text);
+implement!(String, Text);
+
 impl<T> From<Option<T>> for Value
 where
     T: Into<Value>,
@@ -194,3 +457,24 @@ where
         }
     }
 }
+
+// Copy a protected or unprotected value owned by SQLite.
+pub unsafe fn read(raw: *mut ffi::sqlite3_value) -> Value {
+    let bytes = |pointer: *const u8| {
+        let count = ffi::sqlite3_value_bytes(raw) as usize;
+        if pointer.is_null() || count == 0 {
+            &[][..]
+        } else {
+            std::slice::from_raw_parts(pointer, count)
+        }
+    };
+    match ffi::sqlite3_value_type(raw) {
+        ffi::SQLITE_BLOB => Value::Binary(bytes(ffi::sqlite3_value_blob(raw).cast()).to_vec()),
+        ffi::SQLITE_FLOAT => Value::Float(ffi::sqlite3_value_double(raw)),
+        ffi::SQLITE_INTEGER => Value::Integer(ffi::sqlite3_value_int64(raw)),
+        ffi::SQLITE_TEXT => {
+            Value::String(String::from_utf8_lossy(bytes(ffi::sqlite3_value_text(raw))).into_owned())
+        }
+        _ => Value::Null,
+    }
+}
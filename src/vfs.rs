@@ -0,0 +1,848 @@
+use core::ffi::{c_char, c_int, c_void};
+use std::ffi::CString;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::{error, Error, Result};
+
+/// A virtual file system.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::sync::{Arc, Mutex};
+///
+/// use sqlite::{AccessCheck, FileOptions, OpenFlags, Vfs, VfsFile};
+///
+/// type Files = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+///
+/// #[derive(Default)]
+/// struct Memory(Files);
+///
+/// struct MemoryFile {
+///     files: Files,
+///     path: String,
+/// }
+///
+/// impl Vfs for Memory {
+///     type File = MemoryFile;
+///
+///     fn open(&self, path: Option<&str>, _: FileOptions) -> sqlite::Result<MemoryFile> {
+///         let path = path.unwrap_or("temporary").to_string();
+///         self.0.lock().unwrap().entry(path.clone()).or_default();
+///         Ok(MemoryFile { files: self.0.clone(), path })
+///     }
+///
+///     fn delete(&self, path: &str, _: bool) -> sqlite::Result<()> {
+///         self.0.lock().unwrap().remove(path);
+///         Ok(())
+///     }
+///
+///     fn access(&self, path: &str, _: AccessCheck) -> sqlite::Result<bool> {
+///         Ok(self.0.lock().unwrap().contains_key(path))
+///     }
+/// }
+///
+/// impl VfsFile for MemoryFile {
+///     fn read(&mut self, buffer: &mut [u8], offset: u64) -> sqlite::Result<usize> {
+///         let files = self.files.lock().unwrap();
+///         let data = files[&self.path].get(offset as usize..).unwrap_or_default();
+///         let count = data.len().min(buffer.len());
+///         buffer[..count].copy_from_slice(&data[..count]);
+///         Ok(count)
+///     }
+///
+///     fn write(&mut self, data: &[u8], offset: u64) -> sqlite::Result<()> {
+///         let mut files = self.files.lock().unwrap();
+///         let file = files.get_mut(&self.path).unwrap();
+///         let end = offset as usize + data.len();
+///         if file.len() < end {
+///             file.resize(end, 0);
+///         }
+///         file[offset as usize..end].copy_from_slice(data);
+///         Ok(())
+///     }
+///
+///     fn truncate(&mut self, size: u64) -> sqlite::Result<()> {
+///         let mut files = self.files.lock().unwrap();
+///         files.get_mut(&self.path).unwrap().truncate(size as usize);
+///         Ok(())
+///     }
+///
+///     fn sync(&mut self, _: bool) -> sqlite::Result<()> {
+///         Ok(())
+///     }
+///
+///     fn file_size(&self) -> sqlite::Result<u64> {
+///         Ok(self.files.lock().unwrap()[&self.path].len() as u64)
+///     }
+/// }
+///
+/// sqlite::register_vfs("example", Memory::default(), false)?;
+/// let flags = OpenFlags::new().with_create().with_read_write();
+/// let connection = sqlite::Connection::open_with_flags_and_vfs("database", flags, "example")?;
+/// connection.execute("CREATE TABLE users (name TEXT)")?;
+/// # Ok::<(), sqlite::Error>(())
+/// ```
+pub trait Vfs: Send + Sync + 'static {
+    /// The type of files.
+    type File: VfsFile;
+
+    /// Open a file.
+    ///
+    /// The path is absent in case of temporary files, which are to be deleted when closed.
+    fn open(&self, path: Option<&str>, options: FileOptions) -> Result<Self::File>;
+
+    /// Delete a file.
+    fn delete(&self, path: &str, sync_directory: bool) -> Result<()>;
+
+    /// Check if a file exists or is accessible.
+    fn access(&self, path: &str, check: AccessCheck) -> Result<bool>;
+
+    /// Convert a path into a full path.
+    ///
+    /// The default implementation returns the path as is.
+    #[inline]
+    fn full_pathname(&self, path: &str) -> Result<String> {
+        Ok(path.into())
+    }
+
+    /// Fill a buffer with random bytes.
+    fn randomness(&self, buffer: &mut [u8]) {
+        use std::hash::{BuildHasher, Hasher};
+
+        let state = std::collections::hash_map::RandomState::new();
+        for (index, chunk) in buffer.chunks_mut(8).enumerate() {
+            let mut hasher = state.build_hasher();
+            hasher.write_usize(index);
+            let bytes = hasher.finish().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    /// Sleep for a period of time and return the time slept.
+    #[inline]
+    fn sleep(&self, duration: Duration) -> Duration {
+        std::thread::sleep(duration);
+        duration
+    }
+
+    /// Return the current time.
+    #[inline]
+    fn current_time(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A file of a virtual file system.
+///
+/// Errors without a code are reported to SQLite as the I/O error corresponding to the operation.
+pub trait VfsFile: Send + 'static {
+    /// Indicate if the file supports shared memory, which is required for write-ahead logging
+    /// unless the database is opened in the exclusive locking mode.
+    const SHARED_MEMORY: bool = false;
+
+    /// Read data at an offset and return the number of bytes read.
+    ///
+    /// Reading fewer bytes than requested indicates the end of the file.
+    fn read(&mut self, buffer: &mut [u8], offset: u64) -> Result<usize>;
+
+    /// Write data at an offset.
+    fn write(&mut self, data: &[u8], offset: u64) -> Result<()>;
+
+    /// Truncate or extend the file to a size.
+    fn truncate(&mut self, size: u64) -> Result<()>;
+
+    /// Flush the file to persistent storage.
+    fn sync(&mut self, data_only: bool) -> Result<()>;
+
+    /// Return the size of the file.
+    fn file_size(&self) -> Result<u64>;
+
+    /// Raise the lock to a level.
+    ///
+    /// If the lock cannot be obtained, the function is expected to return an error with code
+    /// `SQLITE_BUSY`. The default implementation does nothing.
+    #[inline]
+    fn lock(&mut self, _: LockLevel) -> Result<()> {
+        Ok(())
+    }
+
+    /// Lower the lock to a level, which is either `LockLevel::Shared` or `LockLevel::None`.
+    ///
+    /// The default implementation does nothing.
+    #[inline]
+    fn unlock(&mut self, _: LockLevel) -> Result<()> {
+        Ok(())
+    }
+
+    /// Check if any connection holds a lock at `LockLevel::Reserved` or above.
+    ///
+    /// The default implementation returns `false`.
+    #[inline]
+    fn check_reserved_lock(&self) -> Result<bool> {
+        Ok(false)
+    }
+
+    /// Return the sector size.
+    #[inline]
+    fn sector_size(&self) -> usize {
+        4096
+    }
+
+    /// Map a region of shared memory.
+    ///
+    /// Shared memory consists of regions of equal size and is shared by all files opened for the
+    /// same database. If the region does not exist, it is to be allocated via `ShmRegion::new`
+    /// when `extend` is true; otherwise, `None` is to be returned. The region is kept alive until
+    /// `VfsFile::shm_unmap` and is expected to be at least of the requested size. The default
+    /// implementation fails.
+    #[inline]
+    fn shm_map(
+        &mut self,
+        _region: usize,
+        _size: usize,
+        _extend: bool,
+    ) -> Result<Option<ShmRegion>> {
+        raise!("shared memory is not supported");
+    }
+
+    /// Acquire locks on consecutive slots of shared memory.
+    ///
+    /// If the locks cannot be obtained, the function is expected to return an error with code
+    /// `SQLITE_BUSY`. The default implementation fails.
+    #[inline]
+    fn shm_lock(&mut self, _offset: usize, _count: usize, _exclusive: bool) -> Result<()> {
+        raise!("shared memory is not supported");
+    }
+
+    /// Release locks on consecutive slots of shared memory.
+    ///
+    /// The default implementation fails.
+    #[inline]
+    fn shm_unlock(&mut self, _offset: usize, _count: usize, _exclusive: bool) -> Result<()> {
+        raise!("shared memory is not supported");
+    }
+
+    /// Ensure that all prior reads and writes of shared memory are complete.
+    #[inline]
+    fn shm_barrier(&self) {
+        std::sync::atomic::fence(std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// Unmap shared memory, deleting it if requested.
+    ///
+    /// The default implementation does nothing.
+    #[inline]
+    fn shm_unmap(&mut self, _delete: bool) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A region of shared memory.
+///
+/// The memory is filled with zeros upon creation, and clones refer to the same memory.
+#[derive(Clone)]
+pub struct ShmRegion(Arc<Memory>);
+
+/// A check performed by `Vfs::access`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessCheck {
+    /// Check if the file exists.
+    Exists,
+    /// Check if the file is readable and writable.
+    ReadWrite,
+    /// Check if the file is readable.
+    Read,
+}
+
+/// A kind of file opened via a virtual file system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileKind {
+    /// A main database.
+    MainDb,
+    /// A rollback journal of a main database.
+    MainJournal,
+    /// A temporary database.
+    TempDb,
+    /// A rollback journal of a temporary database.
+    TempJournal,
+    /// A transient database.
+    TransientDb,
+    /// A statement journal.
+    SubJournal,
+    /// A super-journal of a multi-database transaction.
+    SuperJournal,
+    /// A write-ahead log.
+    Wal,
+    /// Any other file.
+    Other,
+}
+
+/// Options for opening a file via a virtual file system.
+#[derive(Clone, Copy, Debug)]
+pub struct FileOptions(pub(crate) c_int);
+
+/// A lock level of a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LockLevel {
+    /// No lock.
+    None,
+    /// A lock for reading.
+    Shared,
+    /// A lock for writing in the future.
+    Reserved,
+    /// A lock for writing once all shared locks are released.
+    Pending,
+    /// A lock for writing.
+    Exclusive,
+}
+
+#[repr(C)]
+struct Data<T: Vfs> {
+    base: ffi::sqlite3_vfs,
+    methods: ffi::sqlite3_io_methods,
+    name: CString,
+    vfs: T,
+}
+
+// The file is boxed, since SQLite guarantees only an alignment of eight bytes.
+#[repr(C)]
+struct File<T: Vfs> {
+    base: ffi::sqlite3_file,
+    file: Box<T::File>,
+    regions: Vec<Option<ShmRegion>>,
+}
+
+struct Memory(*mut [u8]);
+
+impl FileOptions {
+    /// Return the kind of the file.
+    pub fn kind(&self) -> FileKind {
+        match self.0 & 0x000f_ff00 {
+            ffi::SQLITE_OPEN_MAIN_DB => FileKind::MainDb,
+            ffi::SQLITE_OPEN_MAIN_JOURNAL => FileKind::MainJournal,
+            ffi::SQLITE_OPEN_TEMP_DB => FileKind::TempDb,
+            ffi::SQLITE_OPEN_TEMP_JOURNAL => FileKind::TempJournal,
+            ffi::SQLITE_OPEN_TRANSIENT_DB => FileKind::TransientDb,
+            ffi::SQLITE_OPEN_SUBJOURNAL => FileKind::SubJournal,
+            ffi::SQLITE_OPEN_SUPER_JOURNAL => FileKind::SuperJournal,
+            ffi::SQLITE_OPEN_WAL => FileKind::Wal,
+            _ => FileKind::Other,
+        }
+    }
+
+    /// Check if the file is to be created if it does not exist.
+    #[inline]
+    pub fn is_create(&self) -> bool {
+        self.0 & ffi::SQLITE_OPEN_CREATE != 0
+    }
+
+    /// Check if the file is to be deleted when closed.
+    #[inline]
+    pub fn is_delete_on_close(&self) -> bool {
+        self.0 & ffi::SQLITE_OPEN_DELETEONCLOSE != 0
+    }
+
+    /// Check if the file is required not to exist.
+    #[inline]
+    pub fn is_exclusive(&self) -> bool {
+        self.0 & ffi::SQLITE_OPEN_EXCLUSIVE != 0
+    }
+
+    /// Check if the file is to be opened for reading only.
+    #[inline]
+    pub fn is_read_only(&self) -> bool {
+        self.0 & ffi::SQLITE_OPEN_READONLY != 0
+    }
+}
+
+impl ShmRegion {
+    /// Allocate a region of a size.
+    pub fn new(size: usize) -> Self {
+        ShmRegion(Arc::new(Memory(Box::into_raw(
+            vec![0u8; size].into_boxed_slice(),
+        ))))
+    }
+
+    /// Return the size.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0 .0.len()
+    }
+
+    /// Check if the size is zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    fn as_ptr(&self) -> *mut u8 {
+        self.0 .0 as *mut u8
+    }
+}
+
+impl Drop for Memory {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { drop(Box::from_raw(self.0)) };
+    }
+}
+
+// The memory is accessed only by SQLite, which synchronizes the access via shared memory locks.
+unsafe impl Send for Memory {}
+
+unsafe impl Sync for Memory {}
+
+impl LockLevel {
+    fn from_raw(level: c_int) -> Self {
+        match level {
+            ffi::SQLITE_LOCK_SHARED => LockLevel::Shared,
+            ffi::SQLITE_LOCK_RESERVED => LockLevel::Reserved,
+            ffi::SQLITE_LOCK_PENDING => LockLevel::Pending,
+            ffi::SQLITE_LOCK_EXCLUSIVE => LockLevel::Exclusive,
+            _ => LockLevel::None,
+        }
+    }
+}
+
+// Serialize registrations for the check for an existing name and the registration itself to be
+// atomic with respect to other registrations via the crate.
+static REGISTRATION: Mutex<()> = Mutex::new(());
+
+pub fn register<T: Vfs>(name: &str, vfs: T, make_default: bool) -> Result<()> {
+    const { assert!(std::mem::align_of::<File<T>>() <= 8) };
+    let name = str_to_cstr!(name);
+    let _guard = lock(&REGISTRATION);
+    unsafe {
+        if !ffi::sqlite3_vfs_find(name.as_ptr()).is_null() {
+            raise!("a virtual file system with the same name already exists");
+        }
+    }
+    let data = Box::into_raw(Box::new(Data {
+        base: ffi::sqlite3_vfs {
+            iVersion: 2,
+            szOsFile: std::mem::size_of::<File<T>>() as c_int,
+            mxPathname: 1024,
+            pNext: std::ptr::null_mut(),
+            zName: name.as_ptr(),
+            pAppData: std::ptr::null_mut(),
+            xOpen: Some(open_callback::<T>),
+            xDelete: Some(delete_callback::<T>),
+            xAccess: Some(access_callback::<T>),
+            xFullPathname: Some(full_pathname_callback::<T>),
+            xDlOpen: Some(dl_open_callback),
+            xDlError: Some(dl_error_callback),
+            xDlSym: Some(dl_sym_callback),
+            xDlClose: Some(dl_close_callback),
+            xRandomness: Some(randomness_callback::<T>),
+            xSleep: Some(sleep_callback::<T>),
+            xCurrentTime: Some(current_time_callback::<T>),
+            xGetLastError: Some(get_last_error_callback),
+            xCurrentTimeInt64: Some(current_time_int64_callback::<T>),
+            xSetSystemCall: None,
+            xGetSystemCall: None,
+            xNextSystemCall: None,
+        },
+        methods: ffi::sqlite3_io_methods {
+            iVersion: if T::File::SHARED_MEMORY { 2 } else { 1 },
+            xClose: Some(close_callback::<T>),
+            xRead: Some(read_callback::<T>),
+            xWrite: Some(write_callback::<T>),
+            xTruncate: Some(truncate_callback::<T>),
+            xSync: Some(sync_callback::<T>),
+            xFileSize: Some(file_size_callback::<T>),
+            xLock: Some(lock_callback::<T>),
+            xUnlock: Some(unlock_callback::<T>),
+            xCheckReservedLock: Some(check_reserved_lock_callback::<T>),
+            xFileControl: Some(file_control_callback),
+            xSectorSize: Some(sector_size_callback::<T>),
+            xDeviceCharacteristics: Some(device_characteristics_callback),
+            xShmMap: Some(shm_map_callback::<T>),
+            xShmLock: Some(shm_lock_callback::<T>),
+            xShmBarrier: Some(shm_barrier_callback::<T>),
+            xShmUnmap: Some(shm_unmap_callback::<T>),
+            xFetch: None,
+            xUnfetch: None,
+        },
+        name,
+        vfs,
+    }));
+    unsafe {
+        let code = ffi::sqlite3_vfs_register(&mut (*data).base, c_int::from(make_default));
+        if code != ffi::SQLITE_OK {
+            drop(Box::from_raw(data));
+            return Err(error(code));
+        }
+    }
+    Ok(())
+}
+
+// Lock a mutex regardless of whether a thread has panicked while holding it.
+pub(crate) fn lock<T>(value: &Mutex<T>) -> MutexGuard<'_, T> {
+    value.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+fn code(error: Error, default: c_int) -> c_int {
+    error.code.map_or(default, |code| code as c_int)
+}
+
+unsafe fn path<'l>(path: *const c_char) -> Option<&'l str> {
+    if path.is_null() {
+        None
+    } else {
+        c_str_to_str!(path).ok()
+    }
+}
+
+unsafe fn vfs<'l, T: Vfs>(vfs: *mut ffi::sqlite3_vfs) -> &'l T {
+    &(*(vfs as *mut Data<T>)).vfs
+}
+
+unsafe fn file<'l, T: Vfs>(file: *mut ffi::sqlite3_file) -> &'l mut T::File {
+    &mut (*(file as *mut File<T>)).file
+}
+
+extern "C" fn open_callback<T: Vfs>(
+    raw: *mut ffi::sqlite3_vfs,
+    name: ffi::sqlite3_filename,
+    file: *mut ffi::sqlite3_file,
+    flags: c_int,
+    output_flags: *mut c_int,
+) -> c_int {
+    unsafe {
+        (*file).pMethods = std::ptr::null();
+        if !name.is_null() && path(name).is_none() {
+            return ffi::SQLITE_CANTOPEN;
+        }
+        match vfs::<T>(raw).open(path(name), FileOptions(flags)) {
+            Ok(value) => {
+                std::ptr::write(
+                    file as *mut File<T>,
+                    File {
+                        base: ffi::sqlite3_file {
+                            pMethods: &(*(raw as *mut Data<T>)).methods,
+                        },
+                        file: Box::new(value),
+                        regions: Vec::new(),
+                    },
+                );
+                if !output_flags.is_null() {
+                    *output_flags = flags;
+                }
+                ffi::SQLITE_OK
+            }
+            Err(error) => code(error, ffi::SQLITE_CANTOPEN),
+        }
+    }
+}
+
+extern "C" fn delete_callback<T: Vfs>(
+    raw: *mut ffi::sqlite3_vfs,
+    name: *const c_char,
+    sync_directory: c_int,
+) -> c_int {
+    unsafe {
+        let Some(name) = path(name) else {
+            return ffi::SQLITE_IOERR_DELETE;
+        };
+        match vfs::<T>(raw).delete(name, sync_directory != 0) {
+            Ok(_) => ffi::SQLITE_OK,
+            Err(error) => code(error, ffi::SQLITE_IOERR_DELETE),
+        }
+    }
+}
+
+extern "C" fn access_callback<T: Vfs>(
+    raw: *mut ffi::sqlite3_vfs,
+    name: *const c_char,
+    flags: c_int,
+    output: *mut c_int,
+) -> c_int {
+    unsafe {
+        let Some(name) = path(name) else {
+            return ffi::SQLITE_IOERR_ACCESS;
+        };
+        let check = match flags {
+            ffi::SQLITE_ACCESS_READWRITE => AccessCheck::ReadWrite,
+            ffi::SQLITE_ACCESS_READ => AccessCheck::Read,
+            _ => AccessCheck::Exists,
+        };
+        match vfs::<T>(raw).access(name, check) {
+            Ok(value) => {
+                *output = c_int::from(value);
+                ffi::SQLITE_OK
+            }
+            Err(error) => code(error, ffi::SQLITE_IOERR_ACCESS),
+        }
+    }
+}
+
+extern "C" fn full_pathname_callback<T: Vfs>(
+    raw: *mut ffi::sqlite3_vfs,
+    name: *const c_char,
+    size: c_int,
+    output: *mut c_char,
+) -> c_int {
+    unsafe {
+        let Some(name) = path(name) else {
+            return ffi::SQLITE_CANTOPEN;
+        };
+        match vfs::<T>(raw).full_pathname(name) {
+            Ok(value) if !value.contains('\0') && value.len() < size as usize => {
+                std::ptr::copy_nonoverlapping(value.as_ptr(), output as *mut u8, value.len());
+                *output.add(value.len()) = 0;
+                ffi::SQLITE_OK
+            }
+            Ok(_) => ffi::SQLITE_CANTOPEN,
+            Err(error) => code(error, ffi::SQLITE_CANTOPEN),
+        }
+    }
+}
+
+extern "C" fn dl_open_callback(_: *mut ffi::sqlite3_vfs, _: *const c_char) -> *mut c_void {
+    std::ptr::null_mut()
+}
+
+extern "C" fn dl_error_callback(_: *mut ffi::sqlite3_vfs, size: c_int, output: *mut c_char) {
+    let message = c"loading extensions is not supported";
+    if size > 0 {
+        let count = message.to_bytes().len().min(size as usize - 1);
+        unsafe {
+            std::ptr::copy_nonoverlapping(message.as_ptr(), output, count);
+            *output.add(count) = 0;
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+extern "C" fn dl_sym_callback(
+    _: *mut ffi::sqlite3_vfs,
+    _: *mut c_void,
+    _: *const c_char,
+) -> Option<unsafe extern "C" fn(*mut ffi::sqlite3_vfs, *mut c_void, *const c_char)> {
+    None
+}
+
+extern "C" fn dl_close_callback(_: *mut ffi::sqlite3_vfs, _: *mut c_void) {}
+
+extern "C" fn randomness_callback<T: Vfs>(
+    raw: *mut ffi::sqlite3_vfs,
+    size: c_int,
+    output: *mut c_char,
+) -> c_int {
+    unsafe {
+        let buffer = std::slice::from_raw_parts_mut(output as *mut u8, size.max(0) as usize);
+        vfs::<T>(raw).randomness(buffer);
+    }
+    size
+}
+
+extern "C" fn sleep_callback<T: Vfs>(raw: *mut ffi::sqlite3_vfs, microseconds: c_int) -> c_int {
+    let duration = Duration::from_micros(microseconds.max(0) as u64);
+    let duration = unsafe { vfs::<T>(raw).sleep(duration) };
+    duration.as_micros().min(c_int::MAX as u128) as c_int
+}
+
+extern "C" fn current_time_callback<T: Vfs>(raw: *mut ffi::sqlite3_vfs, output: *mut f64) -> c_int {
+    let mut time = 0;
+    let code = current_time_int64_callback::<T>(raw, &mut time);
+    unsafe { *output = time as f64 / 86_400_000.0 };
+    code
+}
+
+extern "C" fn current_time_int64_callback<T: Vfs>(
+    raw: *mut ffi::sqlite3_vfs,
+    output: *mut ffi::sqlite3_int64,
+) -> c_int {
+    // The number of milliseconds since the Julian epoch.
+    const UNIX_EPOCH_JULIAN: i64 = 210_866_760_000_000;
+    let time = unsafe { vfs::<T>(raw).current_time() };
+    let milliseconds = match time.duration_since(UNIX_EPOCH) {
+        Ok(value) => value.as_millis() as i64,
+        Err(error) => -(error.duration().as_millis() as i64),
+    };
+    unsafe { *output = UNIX_EPOCH_JULIAN + milliseconds };
+    ffi::SQLITE_OK
+}
+
+extern "C" fn get_last_error_callback(_: *mut ffi::sqlite3_vfs, _: c_int, _: *mut c_char) -> c_int {
+    0
+}
+
+extern "C" fn close_callback<T: Vfs>(file: *mut ffi::sqlite3_file) -> c_int {
+    unsafe { std::ptr::drop_in_place(file as *mut File<T>) };
+    ffi::SQLITE_OK
+}
+
+extern "C" fn read_callback<T: Vfs>(
+    raw: *mut ffi::sqlite3_file,
+    buffer: *mut c_void,
+    size: c_int,
+    offset: ffi::sqlite3_int64,
+) -> c_int {
+    unsafe {
+        let buffer = std::slice::from_raw_parts_mut(buffer as *mut u8, size.max(0) as usize);
+        match file::<T>(raw).read(buffer, offset as u64) {
+            Ok(count) if count >= buffer.len() => ffi::SQLITE_OK,
+            Ok(count) => {
+                // Unread bytes are required to be filled with zeros.
+                buffer[count..].fill(0);
+                ffi::SQLITE_IOERR_SHORT_READ
+            }
+            Err(error) => code(error, ffi::SQLITE_IOERR_READ),
+        }
+    }
+}
+
+extern "C" fn write_callback<T: Vfs>(
+    raw: *mut ffi::sqlite3_file,
+    data: *const c_void,
+    size: c_int,
+    offset: ffi::sqlite3_int64,
+) -> c_int {
+    unsafe {
+        let data = std::slice::from_raw_parts(data as *const u8, size.max(0) as usize);
+        match file::<T>(raw).write(data, offset as u64) {
+            Ok(_) => ffi::SQLITE_OK,
+            Err(error) => code(error, ffi::SQLITE_IOERR_WRITE),
+        }
+    }
+}
+
+extern "C" fn truncate_callback<T: Vfs>(
+    raw: *mut ffi::sqlite3_file,
+    size: ffi::sqlite3_int64,
+) -> c_int {
+    match unsafe { file::<T>(raw).truncate(size as u64) } {
+        Ok(_) => ffi::SQLITE_OK,
+        Err(error) => code(error, ffi::SQLITE_IOERR_TRUNCATE),
+    }
+}
+
+extern "C" fn sync_callback<T: Vfs>(raw: *mut ffi::sqlite3_file, flags: c_int) -> c_int {
+    let data_only = flags & ffi::SQLITE_SYNC_DATAONLY != 0;
+    match unsafe { file::<T>(raw).sync(data_only) } {
+        Ok(_) => ffi::SQLITE_OK,
+        Err(error) => code(error, ffi::SQLITE_IOERR_FSYNC),
+    }
+}
+
+extern "C" fn file_size_callback<T: Vfs>(
+    raw: *mut ffi::sqlite3_file,
+    output: *mut ffi::sqlite3_int64,
+) -> c_int {
+    unsafe {
+        match file::<T>(raw).file_size() {
+            Ok(value) => {
+                *output = value as ffi::sqlite3_int64;
+                ffi::SQLITE_OK
+            }
+            Err(error) => code(error, ffi::SQLITE_IOERR_FSTAT),
+        }
+    }
+}
+
+extern "C" fn lock_callback<T: Vfs>(raw: *mut ffi::sqlite3_file, level: c_int) -> c_int {
+    match unsafe { file::<T>(raw).lock(LockLevel::from_raw(level)) } {
+        Ok(_) => ffi::SQLITE_OK,
+        Err(error) => code(error, ffi::SQLITE_IOERR_LOCK),
+    }
+}
+
+extern "C" fn unlock_callback<T: Vfs>(raw: *mut ffi::sqlite3_file, level: c_int) -> c_int {
+    match unsafe { file::<T>(raw).unlock(LockLevel::from_raw(level)) } {
+        Ok(_) => ffi::SQLITE_OK,
+        Err(error) => code(error, ffi::SQLITE_IOERR_UNLOCK),
+    }
+}
+
+extern "C" fn check_reserved_lock_callback<T: Vfs>(
+    raw: *mut ffi::sqlite3_file,
+    output: *mut c_int,
+) -> c_int {
+    unsafe {
+        match file::<T>(raw).check_reserved_lock() {
+            Ok(value) => {
+                *output = c_int::from(value);
+                ffi::SQLITE_OK
+            }
+            Err(error) => code(error, ffi::SQLITE_IOERR_CHECKRESERVEDLOCK),
+        }
+    }
+}
+
+extern "C" fn file_control_callback(_: *mut ffi::sqlite3_file, _: c_int, _: *mut c_void) -> c_int {
+    ffi::SQLITE_NOTFOUND
+}
+
+extern "C" fn sector_size_callback<T: Vfs>(raw: *mut ffi::sqlite3_file) -> c_int {
+    unsafe { file::<T>(raw).sector_size() as c_int }
+}
+
+extern "C" fn device_characteristics_callback(_: *mut ffi::sqlite3_file) -> c_int {
+    0
+}
+
+extern "C" fn shm_map_callback<T: Vfs>(
+    raw: *mut ffi::sqlite3_file,
+    region: c_int,
+    size: c_int,
+    extend: c_int,
+    output: *mut *mut c_void,
+) -> c_int {
+    unsafe {
+        let file = &mut *(raw as *mut File<T>);
+        let (region, size) = (region as usize, size as usize);
+        match file.file.shm_map(region, size, extend != 0) {
+            Ok(Some(value)) if value.len() < size => ffi::SQLITE_IOERR_SHMMAP,
+            Ok(Some(value)) => {
+                *output = value.as_ptr() as *mut _;
+                if file.regions.len() <= region {
+                    file.regions.resize(region + 1, None);
+                }
+                file.regions[region] = Some(value);
+                ffi::SQLITE_OK
+            }
+            Ok(None) => {
+                *output = std::ptr::null_mut();
+                ffi::SQLITE_OK
+            }
+            Err(error) => code(error, ffi::SQLITE_IOERR_SHMMAP),
+        }
+    }
+}
+
+extern "C" fn shm_lock_callback<T: Vfs>(
+    raw: *mut ffi::sqlite3_file,
+    offset: c_int,
+    count: c_int,
+    flags: c_int,
+) -> c_int {
+    let file = unsafe { file::<T>(raw) };
+    let exclusive = flags & ffi::SQLITE_SHM_EXCLUSIVE != 0;
+    let result = if flags & ffi::SQLITE_SHM_LOCK != 0 {
+        file.shm_lock(offset as usize, count as usize, exclusive)
+    } else {
+        file.shm_unlock(offset as usize, count as usize, exclusive)
+    };
+    match result {
+        Ok(_) => ffi::SQLITE_OK,
+        Err(error) => code(error, ffi::SQLITE_IOERR_SHMLOCK),
+    }
+}
+
+extern "C" fn shm_barrier_callback<T: Vfs>(raw: *mut ffi::sqlite3_file) {
+    unsafe { file::<T>(raw).shm_barrier() };
+}
+
+extern "C" fn shm_unmap_callback<T: Vfs>(raw: *mut ffi::sqlite3_file, delete: c_int) -> c_int {
+    let file = unsafe { &mut *(raw as *mut File<T>) };
+    file.regions.clear();
+    match file.file.shm_unmap(delete != 0) {
+        Ok(_) => ffi::SQLITE_OK,
+        Err(error) => code(error, ffi::SQLITE_IOERR_SHMMAP),
+    }
+}
@@ -0,0 +1,888 @@
+use core::ffi::{c_char, c_int, c_void};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::connection::Connection;
+use crate::error::{Error, ErrorKind, Result};
+use crate::value::Value;
+
+/// A virtual table.
+///
+/// # Examples
+///
+/// ```
+/// use sqlite::{IndexInfo, Module, VTab, VTabCursor, Value};
+///
+/// struct Numbers(Vec<i64>);
+///
+/// struct NumbersCursor {
+///     values: Vec<i64>,
+///     position: usize,
+/// }
+///
+/// impl VTab for Numbers {
+///     type Aux = Vec<i64>;
+///     type Cursor = NumbersCursor;
+///
+///     fn connect(aux: &Vec<i64>, _: &[&str]) -> sqlite::Result<(String, Self)> {
+///         Ok(("CREATE TABLE x (value INTEGER)".into(), Numbers(aux.clone())))
+///     }
+///
+///     fn best_index(&self, _: &mut IndexInfo) -> sqlite::Result<()> {
+///         Ok(())
+///     }
+///
+///     fn open(&self) -> sqlite::Result<NumbersCursor> {
+///         Ok(NumbersCursor { values: self.0.clone(), position: 0 })
+///     }
+/// }
+///
+/// impl VTabCursor for NumbersCursor {
+///     fn filter(&mut self, _: i32, _: Option<&str>, _: &[Value]) -> sqlite::Result<()> {
+///         self.position = 0;
+///         Ok(())
+///     }
+///
+///     fn next(&mut self) -> sqlite::Result<()> {
+///         self.position += 1;
+///         Ok(())
+///     }
+///
+///     fn eof(&self) -> bool {
+///         self.position >= self.values.len()
+///     }
+///
+///     fn column(&self, _: usize) -> sqlite::Result<Value> {
+///         Ok(Value::Integer(self.values[self.position]))
+///     }
+///
+///     fn rowid(&self) -> sqlite::Result<i64> {
+///         Ok(self.position as i64)
+///     }
+/// }
+///
+/// # let connection = sqlite::open(":memory:").unwrap();
+/// connection.create_module("numbers", Module::<Numbers>::new(vec![4, 2, 42]))?;
+/// connection.execute("CREATE VIRTUAL TABLE numbers USING numbers")?;
+/// let query = "SELECT SUM(value) FROM numbers";
+/// assert_eq!(connection.query_one::<(i64,), _, _>(query, ())?.0, 48);
+/// # Ok::<(), sqlite::Error>(())
+/// ```
+pub trait VTab: Sized + Send + 'static {
+    /// The data shared by all tables of the module.
+    type Aux: Send + 'static;
+    /// The cursor.
+    type Cursor: VTabCursor;
+
+    /// Connect to a table.
+    ///
+    /// The arguments are the name of the module, the name of the database, the name of the
+    /// table, and the arguments given in `CREATE VIRTUAL TABLE`, if any. The function returns a
+    /// `CREATE TABLE` statement declaring the columns of the table together with the table.
+    fn connect(aux: &Self::Aux, arguments: &[&str]) -> Result<(String, Self)>;
+
+    /// Create a table in response to `CREATE VIRTUAL TABLE`.
+    ///
+    /// The default implementation calls `connect`.
+    #[inline]
+    fn create(aux: &Self::Aux, arguments: &[&str]) -> Result<(String, Self)> {
+        Self::connect(aux, arguments)
+    }
+
+    /// Choose a query plan.
+    fn best_index(&self, info: &mut IndexInfo<'_>) -> Result<()>;
+
+    /// Open a cursor.
+    fn open(&self) -> Result<Self::Cursor>;
+
+    /// Insert, update, or delete a row.
+    ///
+    /// In case of insertions, the function returns the rowid of the new row; otherwise, the
+    /// returned value is ignored. The default implementation makes the table read-only.
+    #[inline]
+    fn update(&mut self, _: Update<'_>) -> Result<i64> {
+        raise!("the table is read-only");
+    }
+}
+
+/// A cursor of a virtual table.
+pub trait VTabCursor: Sized {
+    /// Start a search.
+    ///
+    /// The index number and string are the ones set in `VTab::best_index`, and the arguments
+    /// are the values of the constraints assigned to arguments there.
+    fn filter(
+        &mut self,
+        index_number: i32,
+        index_string: Option<&str>,
+        arguments: &[Value],
+    ) -> Result<()>;
+
+    /// Advance to the next row.
+    #[allow(clippy::should_implement_trait)]
+    fn next(&mut self) -> Result<()>;
+
+    /// Check if there are no more rows.
+    fn eof(&self) -> bool;
+
+    /// Read a column of the current row.
+    fn column(&self, index: usize) -> Result<Value>;
+
+    /// Return the rowid of the current row.
+    fn rowid(&self) -> Result<i64>;
+}
+
+/// A module of virtual tables.
+pub struct Module<T: VTab> {
+    aux: T::Aux,
+    eponymous_only: bool,
+}
+
+/// A change to a virtual table.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Update<'l> {
+    /// A deletion of the row with a rowid.
+    Delete(i64),
+    /// An insertion of a row with an optional rowid and values for all columns.
+    Insert {
+        /// The rowid, which is absent if SQLite is to choose one.
+        rowid: Option<i64>,
+        /// The values.
+        values: &'l [Value],
+    },
+    /// An update of a row with values for all columns.
+    Update {
+        /// The current rowid.
+        old_rowid: i64,
+        /// The new rowid.
+        rowid: i64,
+        /// The new values.
+        values: &'l [Value],
+    },
+}
+
+/// Information for choosing a query plan.
+pub struct IndexInfo<'l> {
+    raw: *mut ffi::sqlite3_index_info,
+    phantom: PhantomData<&'l mut ffi::sqlite3_index_info>,
+}
+
+/// A constraint in a query.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IndexConstraint {
+    /// The column, which is absent for the rowid.
+    pub column: Option<usize>,
+    /// The operator.
+    pub operator: ConstraintOperator,
+    /// The flag indicating that the constraint can be used.
+    pub usable: bool,
+}
+
+/// An operator of a constraint.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConstraintOperator {
+    /// `=`
+    Eq,
+    /// `>`
+    Gt,
+    /// `<=`
+    Le,
+    /// `<`
+    Lt,
+    /// `>=`
+    Ge,
+    /// `MATCH`
+    Match,
+    /// `LIKE`
+    Like,
+    /// `GLOB`
+    Glob,
+    /// `REGEXP`
+    Regexp,
+    /// `!=`
+    Ne,
+    /// `IS NOT`
+    IsNot,
+    /// `IS NOT NULL`
+    IsNotNull,
+    /// `IS NULL`
+    IsNull,
+    /// `IS`
+    Is,
+    /// `LIMIT`
+    Limit,
+    /// `OFFSET`
+    Offset,
+    /// An overloaded function.
+    Function(u8),
+}
+
+/// A term of an ORDER BY clause in a query.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IndexOrderBy {
+    /// The column, which is absent for the rowid.
+    pub column: Option<usize>,
+    /// The flag indicating descending order.
+    pub descending: bool,
+}
+
+impl<T: VTab> Module<T> {
+    /// Create a module whose tables are created via `CREATE VIRTUAL TABLE`.
+    #[inline]
+    pub fn new(aux: T::Aux) -> Self {
+        Self {
+            aux,
+            eponymous_only: false,
+        }
+    }
+
+    /// Create a module with a single table named after the module.
+    ///
+    /// The table exists in every database without `CREATE VIRTUAL TABLE`, which is not
+    /// supported.
+    #[inline]
+    pub fn eponymous_only(aux: T::Aux) -> Self {
+        Self {
+            aux,
+            eponymous_only: true,
+        }
+    }
+}
+
+impl IndexInfo<'_> {
+    /// Return the constraints.
+    pub fn constraints(&self) -> impl Iterator<Item = IndexConstraint> + '_ {
+        self.raw_constraints()
+            .iter()
+            .map(|constraint| IndexConstraint {
+                column: usize::try_from(constraint.iColumn).ok(),
+                operator: match constraint.op as c_int {
+                    ffi::SQLITE_INDEX_CONSTRAINT_EQ => ConstraintOperator::Eq,
+                    ffi::SQLITE_INDEX_CONSTRAINT_GT => ConstraintOperator::Gt,
+                    ffi::SQLITE_INDEX_CONSTRAINT_LE => ConstraintOperator::Le,
+                    ffi::SQLITE_INDEX_CONSTRAINT_LT => ConstraintOperator::Lt,
+                    ffi::SQLITE_INDEX_CONSTRAINT_GE => ConstraintOperator::Ge,
+                    ffi::SQLITE_INDEX_CONSTRAINT_MATCH => ConstraintOperator::Match,
+                    ffi::SQLITE_INDEX_CONSTRAINT_LIKE => ConstraintOperator::Like,
+                    ffi::SQLITE_INDEX_CONSTRAINT_GLOB => ConstraintOperator::Glob,
+                    ffi::SQLITE_INDEX_CONSTRAINT_REGEXP => ConstraintOperator::Regexp,
+                    ffi::SQLITE_INDEX_CONSTRAINT_NE => ConstraintOperator::Ne,
+                    ffi::SQLITE_INDEX_CONSTRAINT_ISNOT => ConstraintOperator::IsNot,
+                    ffi::SQLITE_INDEX_CONSTRAINT_ISNOTNULL => ConstraintOperator::IsNotNull,
+                    ffi::SQLITE_INDEX_CONSTRAINT_ISNULL => ConstraintOperator::IsNull,
+                    ffi::SQLITE_INDEX_CONSTRAINT_IS => ConstraintOperator::Is,
+                    ffi::SQLITE_INDEX_CONSTRAINT_LIMIT => ConstraintOperator::Limit,
+                    ffi::SQLITE_INDEX_CONSTRAINT_OFFSET => ConstraintOperator::Offset,
+                    _ => ConstraintOperator::Function(constraint.op),
+                },
+                usable: constraint.usable != 0,
+            })
+    }
+
+    /// Return the terms of the ORDER BY clause.
+    pub fn order_by(&self) -> impl Iterator<Item = IndexOrderBy> + '_ {
+        let raw = unsafe { &*self.raw };
+        slice(raw.aOrderBy, raw.nOrderBy)
+            .iter()
+            .map(|order_by| IndexOrderBy {
+                column: usize::try_from(order_by.iColumn).ok(),
+                descending: order_by.desc != 0,
+            })
+    }
+
+    /// Pass the right-hand value of a constraint to `VTabCursor::filter`.
+    ///
+    /// The constraint is identified by its position among `constraints`, and the argument by
+    /// its position among the arguments of `VTabCursor::filter`, which should be contiguous
+    /// starting from 0. If `omit` is `true`, SQLite does not double-check the constraint.
+    /// Nonexistent constraints are ignored.
+    pub fn set_argument(&mut self, constraint: usize, argument: usize, omit: bool) {
+        let raw = unsafe { &mut *self.raw };
+        if constraint >= raw.nConstraint as usize {
+            return;
+        }
+        unsafe {
+            let usage = &mut *raw.aConstraintUsage.add(constraint);
+            usage.argvIndex = argument as c_int + 1;
+            usage.omit = omit as u8;
+        }
+    }
+
+    /// Set the index number passed to `VTabCursor::filter`.
+    #[inline]
+    pub fn set_index_number(&mut self, value: i32) {
+        unsafe { (*self.raw).idxNum = value };
+    }
+
+    /// Set the index string passed to `VTabCursor::filter`.
+    pub fn set_index_string(&mut self, value: &str) -> Result<()> {
+        let value = str_to_cstr!(value);
+        unsafe {
+            let raw = &mut *self.raw;
+            if raw.needToFreeIdxStr != 0 {
+                ffi::sqlite3_free(raw.idxStr as *mut _);
+            }
+            raw.idxStr = ffi::sqlite3_mprintf(c"%s".as_ptr(), value.as_ptr());
+            raw.needToFreeIdxStr = 1;
+        }
+        Ok(())
+    }
+
+    /// Indicate that the rows are returned in the order of the ORDER BY clause.
+    #[inline]
+    pub fn set_order_by_consumed(&mut self, value: bool) {
+        unsafe { (*self.raw).orderByConsumed = value as c_int };
+    }
+
+    /// Set the estimated cost of the plan.
+    #[inline]
+    pub fn set_estimated_cost(&mut self, value: f64) {
+        unsafe { (*self.raw).estimatedCost = value };
+    }
+
+    /// Set the estimated number of rows returned by the plan.
+    #[inline]
+    pub fn set_estimated_rows(&mut self, value: i64) {
+        unsafe { (*self.raw).estimatedRows = value };
+    }
+
+    /// Indicate that the plan returns at most one row.
+    #[inline]
+    pub fn set_unique(&mut self, value: bool) {
+        unsafe {
+            if value {
+                (*self.raw).idxFlags |= ffi::SQLITE_INDEX_SCAN_UNIQUE;
+            } else {
+                (*self.raw).idxFlags &= !ffi::SQLITE_INDEX_SCAN_UNIQUE;
+            }
+        }
+    }
+
+    fn raw_constraints(&self) -> &[ffi::sqlite3_index_info_sqlite3_index_constraint] {
+        let raw = unsafe { &*self.raw };
+        slice(raw.aConstraint, raw.nConstraint)
+    }
+}
+
+pub fn create<T: VTab>(connection: &Connection, name: &str, module: Module<T>) -> Result<()> {
+    let name = str_to_cstr!(name);
+    let data = Box::into_raw(Box::new(Data::<T> {
+        module: ffi::sqlite3_module {
+            iVersion: 1,
+            xCreate: if module.eponymous_only {
+                None
+            } else {
+                Some(create_callback::<T>)
+            },
+            xConnect: Some(connect_callback::<T>),
+            xBestIndex: Some(best_index_callback::<T>),
+            xDisconnect: Some(disconnect_callback::<T>),
+            xDestroy: Some(disconnect_callback::<T>),
+            xOpen: Some(open_callback::<T>),
+            xClose: Some(close_callback::<T>),
+            xFilter: Some(filter_callback::<T>),
+            xNext: Some(next_callback::<T>),
+            xEof: Some(eof_callback::<T>),
+            xColumn: Some(column_callback::<T>),
+            xRowid: Some(rowid_callback::<T>),
+            xUpdate: Some(update_callback::<T>),
+            xBegin: None,
+            xSync: None,
+            xCommit: None,
+            xRollback: None,
+            xFindFunction: None,
+            xRename: None,
+            xSavepoint: None,
+            xRelease: None,
+            xRollbackTo: None,
+            xShadowName: None,
+            xIntegrity: None,
+        },
+        aux: module.aux,
+    }));
+    unsafe {
+        ok!(
+            connection.as_raw(),
+            ffi::sqlite3_create_module_v2(
+                connection.as_raw(),
+                name.as_ptr(),
+                &(*data).module,
+                data as *mut _,
+                Some(destroy_callback::<T>),
+            )
+        );
+    }
+    Ok(())
+}
+
+pub fn create_function<F, I>(
+    connection: &Connection,
+    name: &str,
+    columns: &[&str],
+    function: F,
+) -> Result<()>
+where
+    F: Fn(&[Value]) -> I + Send + Sync + 'static,
+    I: IntoIterator<Item = Vec<Value>> + 'static,
+{
+    if columns.is_empty() {
+        raise!("expected at least one column");
+    }
+    let mut parameters = 0;
+    let mut visible = 0;
+    let mapping = columns
+        .iter()
+        .map(|column| {
+            let hidden = column
+                .split_whitespace()
+                .skip(1)
+                .any(|token| token.eq_ignore_ascii_case("HIDDEN"));
+            if hidden {
+                parameters += 1;
+                Err(parameters - 1)
+            } else {
+                visible += 1;
+                Ok(visible - 1)
+            }
+        })
+        .collect();
+    // The parameters present are tracked via the bits of the index number.
+    if parameters > 31 {
+        raise!("expected at most 31 parameters");
+    }
+    let function = Arc::new(Function {
+        schema: format!("CREATE TABLE x ({})", columns.join(", ")),
+        mapping,
+        parameters,
+        function,
+    });
+    create(
+        connection,
+        name,
+        Module::<FunctionTable<F, I>>::eponymous_only(function),
+    )
+}
+
+// Set the result of a function or a column.
+pub unsafe fn result(context: *mut ffi::sqlite3_context, value: &Value) {
+    match value {
+        Value::Binary(value) => ffi::sqlite3_result_blob64(
+            context,
+            value.as_ptr() as *const _,
+            value.len() as u64,
+            transient!(),
+        ),
+        Value::Float(value) => ffi::sqlite3_result_double(context, *value),
+        Value::Integer(value) => ffi::sqlite3_result_int64(context, *value),
+        Value::String(value) => ffi::sqlite3_result_text64(
+            context,
+            value.as_ptr() as *const _,
+            value.len() as u64,
+            transient!(),
+            ffi::SQLITE_UTF8 as u8,
+        ),
+        Value::Null => ffi::sqlite3_result_null(context),
+    }
+}
+
+// Copy values passed by SQLite.
+pub unsafe fn values(count: c_int, values: *mut *mut ffi::sqlite3_value) -> Vec<Value> {
+    slice(values, count)
+        .iter()
+        .map(|&value| crate::value::read(value))
+        .collect()
+}
+
+type Construct<T> = fn(&<T as VTab>::Aux, &[&str]) -> Result<(String, T)>;
+
+struct Data<T: VTab> {
+    module: ffi::sqlite3_module,
+    aux: T::Aux,
+}
+
+#[repr(C)]
+struct Table<T: VTab> {
+    base: ffi::sqlite3_vtab,
+    table: T,
+}
+
+#[repr(C)]
+struct Cursor<T: VTab> {
+    base: ffi::sqlite3_vtab_cursor,
+    cursor: T::Cursor,
+}
+
+struct Function<F> {
+    schema: String,
+    // The index of a column among visible columns or parameters.
+    mapping: Vec<std::result::Result<usize, usize>>,
+    parameters: usize,
+    function: F,
+}
+
+struct FunctionTable<F, I> {
+    function: Arc<Function<F>>,
+    phantom: PhantomData<fn() -> I>,
+}
+
+struct FunctionCursor<F, I: IntoIterator> {
+    function: Arc<Function<F>>,
+    arguments: Vec<Value>,
+    rows: Option<I::IntoIter>,
+    row: Option<Vec<Value>>,
+    rowid: i64,
+}
+
+impl<F, I> VTab for FunctionTable<F, I>
+where
+    F: Fn(&[Value]) -> I + Send + Sync + 'static,
+    I: IntoIterator<Item = Vec<Value>> + 'static,
+{
+    type Aux = Arc<Function<F>>;
+    type Cursor = FunctionCursor<F, I>;
+
+    fn connect(aux: &Self::Aux, _: &[&str]) -> Result<(String, Self)> {
+        let table = FunctionTable {
+            function: aux.clone(),
+            phantom: PhantomData,
+        };
+        Ok((aux.schema.clone(), table))
+    }
+
+    fn best_index(&self, info: &mut IndexInfo<'_>) -> Result<()> {
+        let mut assigned = vec![None; self.function.parameters];
+        for (index, constraint) in info.constraints().enumerate() {
+            let parameter = match constraint
+                .column
+                .map(|column| self.function.mapping[column])
+            {
+                Some(Err(parameter)) => parameter,
+                _ => continue,
+            };
+            if constraint.operator != ConstraintOperator::Eq {
+                continue;
+            }
+            if !constraint.usable {
+                // Prefer plans in which all given arguments are available.
+                return Err(Error {
+                    code: Some(ffi::SQLITE_CONSTRAINT as isize),
+                    message: Some("the arguments are not available".into()),
+                    kind: ErrorKind::Other,
+                });
+            }
+            assigned[parameter].get_or_insert(index);
+        }
+        let mut mask = 0;
+        let mut argument = 0;
+        for (parameter, constraint) in assigned.into_iter().enumerate() {
+            if let Some(constraint) = constraint {
+                info.set_argument(constraint, argument, true);
+                mask |= 1 << parameter;
+                argument += 1;
+            }
+        }
+        info.set_index_number(mask);
+        info.set_estimated_cost(1000.0 / (1 + argument) as f64);
+        Ok(())
+    }
+
+    fn open(&self) -> Result<Self::Cursor> {
+        Ok(FunctionCursor {
+            function: self.function.clone(),
+            arguments: Vec::new(),
+            rows: None,
+            row: None,
+            rowid: 0,
+        })
+    }
+}
+
+impl<F, I> VTabCursor for FunctionCursor<F, I>
+where
+    F: Fn(&[Value]) -> I,
+    I: IntoIterator<Item = Vec<Value>>,
+{
+    fn filter(&mut self, mask: i32, _: Option<&str>, arguments: &[Value]) -> Result<()> {
+        let mut arguments = arguments.iter();
+        self.arguments = (0..self.function.parameters)
+            .map(|parameter| match mask & (1 << parameter) {
+                0 => Value::Null,
+                _ => arguments.next().cloned().unwrap_or_default(),
+            })
+            .collect();
+        let mut rows = (self.function.function)(&self.arguments).into_iter();
+        self.row = rows.next();
+        self.rows = Some(rows);
+        self.rowid = 1;
+        Ok(())
+    }
+
+    fn next(&mut self) -> Result<()> {
+        self.row = self.rows.as_mut().and_then(Iterator::next);
+        self.rowid += 1;
+        Ok(())
+    }
+
+    #[inline]
+    fn eof(&self) -> bool {
+        self.row.is_none()
+    }
+
+    fn column(&self, index: usize) -> Result<Value> {
+        let value = match self.function.mapping.get(index) {
+            Some(&Ok(index)) => self.row.as_ref().and_then(|row| row.get(index)),
+            Some(&Err(index)) => self.arguments.get(index),
+            _ => None,
+        };
+        Ok(value.cloned().unwrap_or_default())
+    }
+
+    #[inline]
+    fn rowid(&self) -> Result<i64> {
+        Ok(self.rowid)
+    }
+}
+
+fn slice<'l, T>(pointer: *const T, count: c_int) -> &'l [T] {
+    if pointer.is_null() || count <= 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(pointer, count as usize) }
+    }
+}
+
+unsafe fn message(error: &Error) -> *mut c_char {
+    let message = error.to_string().replace('\0', "");
+    let message = std::ffi::CString::new(message).unwrap_or_default();
+    ffi::sqlite3_mprintf(c"%s".as_ptr(), message.as_ptr())
+}
+
+unsafe fn report(vtab: *mut ffi::sqlite3_vtab, error: Error) -> c_int {
+    ffi::sqlite3_free((*vtab).zErrMsg as *mut _);
+    (*vtab).zErrMsg = message(&error);
+    error.code.map_or(ffi::SQLITE_ERROR, |code| code as c_int)
+}
+
+unsafe fn initialize<T: VTab>(
+    db: *mut ffi::sqlite3,
+    aux: *mut c_void,
+    count: c_int,
+    arguments: *const *const c_char,
+    vtab: *mut *mut ffi::sqlite3_vtab,
+    error: *mut *mut c_char,
+    construct: Construct<T>,
+) -> c_int {
+    let result = slice(arguments, count)
+        .iter()
+        .map(|&argument| match c_str_to_str!(argument) {
+            Ok(argument) => Ok(argument),
+            _ => raise!("failed to process an argument"),
+        })
+        .collect::<Result<Vec<_>>>()
+        .and_then(|arguments| construct(&(*(aux as *mut Data<T>)).aux, &arguments))
+        .and_then(|(schema, table)| {
+            let schema = str_to_cstr!(schema);
+            ok!(db, ffi::sqlite3_declare_vtab(db, schema.as_ptr()));
+            Ok(table)
+        });
+    match result {
+        Ok(table) => {
+            let table = Box::new(Table {
+                base: std::mem::zeroed(),
+                table,
+            });
+            *vtab = Box::into_raw(table) as *mut ffi::sqlite3_vtab;
+            ffi::SQLITE_OK
+        }
+        Err(result) => {
+            *error = message(&result);
+            result.code.map_or(ffi::SQLITE_ERROR, |code| code as c_int)
+        }
+    }
+}
+
+extern "C" fn create_callback<T: VTab>(
+    db: *mut ffi::sqlite3,
+    aux: *mut c_void,
+    count: c_int,
+    arguments: *const *const c_char,
+    vtab: *mut *mut ffi::sqlite3_vtab,
+    error: *mut *mut c_char,
+) -> c_int {
+    unsafe { initialize::<T>(db, aux, count, arguments, vtab, error, T::create) }
+}
+
+extern "C" fn connect_callback<T: VTab>(
+    db: *mut ffi::sqlite3,
+    aux: *mut c_void,
+    count: c_int,
+    arguments: *const *const c_char,
+    vtab: *mut *mut ffi::sqlite3_vtab,
+    error: *mut *mut c_char,
+) -> c_int {
+    unsafe { initialize::<T>(db, aux, count, arguments, vtab, error, T::connect) }
+}
+
+extern "C" fn best_index_callback<T: VTab>(
+    vtab: *mut ffi::sqlite3_vtab,
+    info: *mut ffi::sqlite3_index_info,
+) -> c_int {
+    unsafe {
+        let table = &(*(vtab as *mut Table<T>)).table;
+        let mut info = IndexInfo {
+            raw: info,
+            phantom: PhantomData,
+        };
+        match table.best_index(&mut info) {
+            Ok(_) => ffi::SQLITE_OK,
+            Err(error) => report(vtab, error),
+        }
+    }
+}
+
+extern "C" fn disconnect_callback<T: VTab>(vtab: *mut ffi::sqlite3_vtab) -> c_int {
+    unsafe {
+        let table = Box::from_raw(vtab as *mut Table<T>);
+        ffi::sqlite3_free(table.base.zErrMsg as *mut _);
+    }
+    ffi::SQLITE_OK
+}
+
+extern "C" fn open_callback<T: VTab>(
+    vtab: *mut ffi::sqlite3_vtab,
+    cursor: *mut *mut ffi::sqlite3_vtab_cursor,
+) -> c_int {
+    unsafe {
+        let table = &(*(vtab as *mut Table<T>)).table;
+        match table.open() {
+            Ok(value) => {
+                let value = Box::new(Cursor::<T> {
+                    base: std::mem::zeroed(),
+                    cursor: value,
+                });
+                *cursor = Box::into_raw(value) as *mut ffi::sqlite3_vtab_cursor;
+                ffi::SQLITE_OK
+            }
+            Err(error) => report(vtab, error),
+        }
+    }
+}
+
+extern "C" fn close_callback<T: VTab>(cursor: *mut ffi::sqlite3_vtab_cursor) -> c_int {
+    unsafe { drop(Box::from_raw(cursor as *mut Cursor<T>)) };
+    ffi::SQLITE_OK
+}
+
+extern "C" fn filter_callback<T: VTab>(
+    cursor: *mut ffi::sqlite3_vtab_cursor,
+    index_number: c_int,
+    index_string: *const c_char,
+    count: c_int,
+    arguments: *mut *mut ffi::sqlite3_value,
+) -> c_int {
+    unsafe {
+        let index_string = if index_string.is_null() {
+            None
+        } else {
+            c_str_to_str!(index_string).ok()
+        };
+        let arguments = values(count, arguments);
+        let value = &mut (*(cursor as *mut Cursor<T>)).cursor;
+        match value.filter(index_number, index_string, &arguments) {
+            Ok(_) => ffi::SQLITE_OK,
+            Err(error) => report((*cursor).pVtab, error),
+        }
+    }
+}
+
+extern "C" fn next_callback<T: VTab>(cursor: *mut ffi::sqlite3_vtab_cursor) -> c_int {
+    unsafe {
+        match (*(cursor as *mut Cursor<T>)).cursor.next() {
+            Ok(_) => ffi::SQLITE_OK,
+            Err(error) => report((*cursor).pVtab, error),
+        }
+    }
+}
+
+extern "C" fn eof_callback<T: VTab>(cursor: *mut ffi::sqlite3_vtab_cursor) -> c_int {
+    unsafe { c_int::from((*(cursor as *mut Cursor<T>)).cursor.eof()) }
+}
+
+extern "C" fn column_callback<T: VTab>(
+    cursor: *mut ffi::sqlite3_vtab_cursor,
+    context: *mut ffi::sqlite3_context,
+    index: c_int,
+) -> c_int {
+    unsafe {
+        match (*(cursor as *mut Cursor<T>)).cursor.column(index as usize) {
+            Ok(value) => {
+                result(context, &value);
+                ffi::SQLITE_OK
+            }
+            Err(error) => report((*cursor).pVtab, error),
+        }
+    }
+}
+
+extern "C" fn rowid_callback<T: VTab>(
+    cursor: *mut ffi::sqlite3_vtab_cursor,
+    rowid: *mut ffi::sqlite3_int64,
+) -> c_int {
+    unsafe {
+        match (*(cursor as *mut Cursor<T>)).cursor.rowid() {
+            Ok(value) => {
+                *rowid = value;
+                ffi::SQLITE_OK
+            }
+            Err(error) => report((*cursor).pVtab, error),
+        }
+    }
+}
+
+extern "C" fn update_callback<T: VTab>(
+    vtab: *mut ffi::sqlite3_vtab,
+    count: c_int,
+    arguments: *mut *mut ffi::sqlite3_value,
+    rowid: *mut ffi::sqlite3_int64,
+) -> c_int {
+    unsafe {
+        let table = &mut (*(vtab as *mut Table<T>)).table;
+        let arguments = values(count, arguments);
+        let update = match &arguments[..] {
+            [Value::Integer(rowid)] => Update::Delete(*rowid),
+            [Value::Null, rowid, values @ ..] => Update::Insert {
+                rowid: match rowid {
+                    &Value::Integer(rowid) => Some(rowid),
+                    _ => None,
+                },
+                values,
+            },
+            [Value::Integer(old_rowid), Value::Integer(rowid), values @ ..] => Update::Update {
+                old_rowid: *old_rowid,
+                rowid: *rowid,
+                values,
+            },
+            _ => {
+                let error = Error {
+                    code: Some(ffi::SQLITE_MISMATCH as isize),
+                    message: Some("expected an integer rowid".into()),
+                    kind: Default::default(),
+                };
+                return report(vtab, error);
+            }
+        };
+        match table.update(update) {
+            Ok(value) => {
+                if let Update::Insert { .. } = update {
+                    *rowid = value;
+                }
+                ffi::SQLITE_OK
+            }
+            Err(error) => report(vtab, error),
+        }
+    }
+}
+
+extern "C" fn destroy_callback<T: VTab>(data: *mut c_void) {
+    unsafe { drop(Box::from_raw(data as *mut Data<T>)) };
+}
@@ -0,0 +1,72 @@
+use std::rc::Rc;
+
+use sqlite::Value;
+
+mod common;
+
+use common::setup_users;
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+#[test]
+fn bind() {
+    let connection = setup_users(":memory:");
+    ok!(connection.enable_rarray());
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob', 69.42, NULL, NULL)"));
+    ok!(connection.execute("INSERT INTO users VALUES (3, 'Carol', 24.0, NULL, NULL)"));
+
+    let query = "SELECT name FROM users WHERE id IN rarray(?) ORDER BY id";
+    let mut statement = ok!(connection.prepare(query));
+    for (ids, expected) in [
+        (vec![1i64, 3], vec!["Alice", "Carol"]),
+        (vec![2, 4], vec!["Bob"]),
+        (vec![], vec![]),
+    ] {
+        ok!(statement.reset());
+        ok!(statement.bind((1, Rc::new(ids))));
+        let names = statement
+            .iter()
+            .map(|row| ok!(row).read::<&str, _>(0).to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, expected);
+    }
+
+    let query = "SELECT id FROM users WHERE name IN rarray(:names) ORDER BY id";
+    let mut statement = ok!(connection.prepare(query));
+    ok!(statement.bind((":names", Rc::new(vec!["Bob".to_string(), "Carol".into()]))));
+    let ids = statement
+        .iter()
+        .map(|row| ok!(row).read::<i64, _>(0))
+        .collect::<Vec<_>>();
+    assert_eq!(ids, [2, 3]);
+}
+
+#[test]
+fn select() {
+    let connection = ok!(sqlite::open(":memory:"));
+    let query = "SELECT value FROM rarray(?)";
+    assert!(connection.prepare(query).is_err());
+    ok!(connection.enable_rarray());
+    let mut statement = ok!(connection.prepare(query));
+    let values = Rc::new(vec![Value::Integer(1), Value::Null, "a".into(), 4.2.into()]);
+    ok!(statement.bind((1, values.clone())));
+    let read = statement
+        .iter()
+        .map(|row| ok!(row).take(0))
+        .collect::<Vec<_>>();
+    assert_eq!(&read, &*values);
+
+    ok!(statement.reset());
+    ok!(statement.bind((1, Rc::new(vec![0.5f64, 1.5]))));
+    let read = statement
+        .iter()
+        .map(|row| ok!(row).read::<f64, _>(0))
+        .collect::<Vec<_>>();
+    assert_eq!(read, [0.5, 1.5]);
+
+    ok!(statement.reset());
+    ok!(statement.bind((1, "not an array")));
+    assert_eq!(statement.iter().count(), 0);
+    drop(statement);
+    assert_eq!(Rc::strong_count(&values), 1);
+}
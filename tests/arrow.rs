@@ -0,0 +1,236 @@
+#![cfg(feature = "arrow")]
+
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Float32Type, Float64Type, Int64Type, UInt8Type};
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, Float32Array, Int64Array, LargeStringArray, RecordBatch,
+    UInt64Array,
+};
+use arrow_schema::{DataType, Field, Schema};
+use sqlite::{Connection, ErrorKind};
+
+mod common;
+
+use common::setup_users;
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+#[test]
+fn insert_record_batch() {
+    let connection = ok!(Connection::open(":memory:"));
+    ok!(connection.execute("CREATE TABLE data (a INTEGER, b REAL, c TEXT, d BOOLEAN)"));
+    let batch = ok!(RecordBatch::try_from_iter([
+        ("a", Arc::new(Int64Array::from(vec![1, 2, 3])) as ArrayRef),
+        (
+            "b",
+            Arc::new(Float32Array::from(vec![Some(0.5), None, Some(2.0)])) as ArrayRef
+        ),
+        (
+            "c",
+            Arc::new(LargeStringArray::from(vec!["x", "y", "z"])) as ArrayRef
+        ),
+        (
+            "d",
+            Arc::new(BooleanArray::from(vec![true, false, true])) as ArrayRef
+        ),
+    ]));
+    assert_eq!(ok!(connection.insert_record_batch("data", &batch)), 3);
+
+    let mut statement = ok!(connection.prepare("SELECT * FROM data ORDER BY a"));
+    let batches = ok!(statement.to_record_batches(10, None));
+    assert_eq!(batches.len(), 1);
+    let fields = batches[0]
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| field.data_type().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fields,
+        [
+            DataType::Int64,
+            DataType::Float64,
+            DataType::Utf8,
+            DataType::Boolean,
+        ],
+    );
+    let b = batches[0].column(1).as_primitive::<Float64Type>();
+    assert_eq!(b.value(0), 0.5);
+    assert!(b.is_null(1));
+    assert_eq!(batches[0].column(2).as_string::<i32>().value(2), "z");
+    assert!(!batches[0].column(3).as_boolean().value(1));
+
+    let batch = ok!(RecordBatch::try_from_iter([
+        ("a", Arc::new(Int64Array::from(vec![4, 5])) as ArrayRef),
+        ("e", Arc::new(Int64Array::from(vec![4, 5])) as ArrayRef),
+    ]));
+    assert!(connection.insert_record_batch("data", &batch).is_err());
+
+    ok!(connection.execute("CREATE TABLE unsigned (a INTEGER)"));
+    let batch = ok!(RecordBatch::try_from_iter([(
+        "a",
+        Arc::new(UInt64Array::from(vec![1, u64::MAX])) as ArrayRef,
+    )]));
+    assert!(connection.insert_record_batch("unsigned", &batch).is_err());
+    let count = ok!(connection.query_one::<(i64,), _, _>("SELECT COUNT(*) FROM unsigned", ()));
+    assert_eq!(count.0, 0);
+}
+
+#[test]
+fn to_record_batches() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob', NULL, NULL, 'bob@example.com')"));
+    ok!(connection.execute("INSERT INTO users VALUES (3, 'Carol', 7, X'01', NULL)"));
+
+    let query = "SELECT id, age, photo, email, id * 1.5, NULL, 'a' || id FROM users ORDER BY id";
+    let mut statement = ok!(connection.prepare(query));
+    let batches = ok!(statement.to_record_batches(2, None));
+    assert_eq!(batches.len(), 2);
+    assert_eq!(batches[0].num_rows(), 2);
+    assert_eq!(batches[1].num_rows(), 1);
+    let schema = batches[0].schema();
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| (field.name().as_str(), field.data_type().clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        fields,
+        [
+            ("id", DataType::Int64),
+            ("age", DataType::Float64),
+            ("photo", DataType::Binary),
+            ("email", DataType::Utf8),
+            ("id * 1.5", DataType::Float64),
+            ("NULL", DataType::Utf8),
+            ("'a' || id", DataType::Utf8),
+        ],
+    );
+    assert_eq!(batches[1].schema(), schema);
+    assert_eq!(
+        batches[1].column(1).as_primitive::<Float64Type>().value(0),
+        7.0
+    );
+    assert_eq!(
+        batches[0].column(2).as_binary::<i32>().value(0),
+        b"\x42\x69"
+    );
+    assert_eq!(
+        batches[0].column(3).as_string::<i32>().value(1),
+        "bob@example.com"
+    );
+    assert_eq!(batches[1].column(6).as_string::<i32>().value(0), "a3");
+
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::UInt8, false),
+        Field::new("name", DataType::LargeBinary, true),
+    ]);
+    let mut statement = ok!(connection.prepare("SELECT id, name FROM users ORDER BY id"));
+    let batches = ok!(statement.to_record_batches(10, Some(&schema)));
+    assert_eq!(
+        batches[0].column(0).as_primitive::<UInt8Type>().values(),
+        &[1, 2, 3]
+    );
+    assert_eq!(batches[0].column(1).as_binary::<i64>().value(1), b"Bob");
+
+    let query = "
+        SELECT CASE id WHEN 1 THEN X'01' WHEN 2 THEN 'b' ELSE 3 END,
+               CASE id WHEN 1 THEN 'a' ELSE 2 END
+        FROM users ORDER BY id
+    ";
+    let mut statement = ok!(connection.prepare(query));
+    let batches = ok!(statement.to_record_batches(10, None));
+    let column = batches[0].column(0).as_binary::<i32>();
+    assert_eq!(column.value(0), b"\x01");
+    assert_eq!(column.value(1), b"b");
+    assert_eq!(column.value(2), b"3");
+    let column = batches[0].column(1).as_string::<i32>();
+    assert_eq!(column.value(0), "a");
+    assert_eq!(column.value(1), "2");
+
+    let mut statement = ok!(connection.prepare("SELECT id, NULL AS note FROM users"));
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("note", DataType::Null, true),
+    ]);
+    let batches = ok!(statement.to_record_batches(10, Some(&schema)));
+    assert_eq!(batches[0].column(1).data_type(), &DataType::Null);
+    ok!(connection.execute("CREATE TABLE notes (id INTEGER, note TEXT)"));
+    assert_eq!(
+        ok!(connection.insert_record_batch("main.notes", &batches[0])),
+        3
+    );
+    let query = "SELECT COUNT(*) FROM notes WHERE note IS NULL";
+    let count = ok!(connection.query_one::<(i64,), _, _>(query, ()));
+    assert_eq!(count.0, 3);
+
+    let mut statement = ok!(connection.prepare("SELECT name FROM users"));
+    let schema = Schema::new(vec![Field::new("name", DataType::Int64, true)]);
+    assert!(statement.to_record_batches(10, Some(&schema)).is_err());
+
+    let mut statement = ok!(connection.prepare("SELECT email FROM users ORDER BY id"));
+    let schema = Schema::new(vec![Field::new("email", DataType::Utf8, false)]);
+    assert!(statement.to_record_batches(10, Some(&schema)).is_err());
+
+    let mut statement = ok!(connection.prepare("SELECT * FROM users WHERE id > 3"));
+    assert!(ok!(statement.to_record_batches(10, None)).is_empty());
+
+    let mut statement = ok!(connection.prepare("SELECT id FROM users"));
+    let batches = ok!(statement.to_record_batches(0, None));
+    assert_eq!(batches.len(), 3);
+    assert_eq!(batches[2].column(0).as_primitive::<Int64Type>().value(0), 3);
+}
+
+#[test]
+fn to_record_batches_with_mismatched_values() {
+    let connection = ok!(sqlite::open(":memory:"));
+    ok!(connection.execute(
+        "
+        CREATE TABLE readings (id INTEGER, value INTEGER);
+        INSERT INTO readings VALUES (1, 10), (2, 20), (3, 30), (4, 'N/A');
+        ",
+    ));
+
+    let query = "SELECT id, value FROM readings ORDER BY id";
+    let mut statement = ok!(connection.prepare(query));
+    let batches = ok!(statement.to_record_batches(10, None));
+    assert_eq!(batches[0].schema().field(0).data_type(), &DataType::Int64);
+    assert_eq!(batches[0].schema().field(1).data_type(), &DataType::Utf8);
+    let column = batches[0].column(1).as_string::<i32>();
+    assert_eq!(column.value(0), "10");
+    assert_eq!(column.value(3), "N/A");
+
+    let mut statement = ok!(connection.prepare(query));
+    let error = statement.to_record_batches(2, None).unwrap_err();
+    assert_eq!(
+        error.message.as_deref(),
+        Some("failed to convert String(\"N/A\") in column value at row 3"),
+    );
+}
+
+#[test]
+fn to_record_batches_with_out_of_range_values() {
+    let connection = ok!(sqlite::open(":memory:"));
+    let schema = Schema::new(vec![Field::new("value", DataType::Float32, true)]);
+
+    let mut statement = ok!(connection.prepare("SELECT 1e300 AS value"));
+    let error = statement.to_record_batches(10, Some(&schema)).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::OutOfRange);
+
+    let mut statement = ok!(connection.prepare("SELECT 2 AS value"));
+    let batches = ok!(statement.to_record_batches(10, Some(&schema)));
+    assert_eq!(
+        batches[0].column(0).as_primitive::<Float32Type>().value(0),
+        2.0
+    );
+
+    let schema = Schema::new(vec![Field::new("value", DataType::Null, true)]);
+    let mut statement = ok!(connection.prepare("SELECT 1 AS value"));
+    let error = statement.to_record_batches(10, Some(&schema)).unwrap_err();
+    assert_eq!(
+        error.message.as_deref(),
+        Some("failed to convert Integer(1) in column value at row 0"),
+    );
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use sqlite::{Connection, State, Value};
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+fn date_time() -> NaiveDateTime {
+    ok!(NaiveDate::from_ymd_opt(2024, 2, 29))
+        .and_time(ok!(NaiveTime::from_hms_milli_opt(13, 14, 15, 500)))
+}
+
+#[test]
+fn bind() {
+    let connection = ok!(Connection::open(":memory:"));
+    let query = "SELECT ?, ?, ?, ?, ?, datetime(?2)";
+    let mut statement = ok!(connection.prepare(query));
+    let offset = ok!(FixedOffset::east_opt(2 * 3600));
+    ok!(statement.bind((1, date_time().and_utc())));
+    ok!(statement.bind((2, ok!(date_time().and_local_timezone(offset).single()))));
+    ok!(statement.bind((3, date_time())));
+    ok!(statement.bind((4, date_time().date())));
+    ok!(statement.bind((5, date_time().time())));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(ok!(statement.read::<String, _>(0)), "2024-02-29 13:14:15.5");
+    assert_eq!(
+        ok!(statement.read::<String, _>(1)),
+        "2024-02-29 13:14:15.5+02:00"
+    );
+    assert_eq!(ok!(statement.read::<String, _>(2)), "2024-02-29 13:14:15.5");
+    assert_eq!(ok!(statement.read::<String, _>(3)), "2024-02-29");
+    assert_eq!(ok!(statement.read::<String, _>(4)), "13:14:15.5");
+    assert_eq!(ok!(statement.read::<String, _>(5)), "2024-02-29 11:14:15");
+}
+
+#[test]
+fn read() {
+    let connection = ok!(Connection::open(":memory:"));
+    let query = "
+        SELECT
+            '2024-02-29 13:14:15.500',
+            '2024-02-29T15:14:15.5+02:00',
+            julianday('2024-02-29 13:14:15.500'),
+            unixepoch('2024-02-29 13:14:15'),
+            NULL
+    ";
+    let mut statement = ok!(connection.prepare(query));
+    assert_eq!(ok!(statement.next()), State::Row);
+    for index in 0..3 {
+        assert_eq!(
+            ok!(statement.read::<DateTime<Utc>, _>(index)),
+            date_time().and_utc()
+        );
+        assert_eq!(ok!(statement.read::<NaiveDateTime, _>(index)), date_time());
+        assert_eq!(
+            ok!(statement.read::<NaiveDate, _>(index)),
+            date_time().date()
+        );
+        assert_eq!(
+            ok!(statement.read::<NaiveTime, _>(index)),
+            date_time().time()
+        );
+    }
+    let value = ok!(statement.read::<DateTime<FixedOffset>, _>(1));
+    assert_eq!(value.offset().local_minus_utc(), 2 * 3600);
+    assert_eq!(value.naive_utc(), date_time());
+    assert_eq!(
+        ok!(statement.read::<NaiveDateTime, _>(3)),
+        date_time().with_nanosecond(0).unwrap()
+    );
+    assert_eq!(ok!(statement.read::<Option<NaiveDate>, _>(4)), None);
+}
+
+#[test]
+fn try_from() {
+    macro_rules! convert(
+        ($value:expr) => (NaiveDateTime::try_from(&Value::from($value)));
+    );
+
+    let date = ok!(NaiveDate::from_ymd_opt(2024, 2, 29));
+    let time = ok!(NaiveTime::from_hms_opt(13, 14, 0));
+    assert_eq!(ok!(convert!("2024-02-29")), date.and_time(NaiveTime::MIN));
+    assert_eq!(ok!(convert!("2024-02-29 13:14")), date.and_time(time));
+    assert_eq!(ok!(convert!(" 2024-02-29T13:14Z ")), date.and_time(time));
+    assert_eq!(
+        ok!(convert!("2024-02-29 14:14 +01:00")),
+        date.and_time(time)
+    );
+    assert_eq!(
+        ok!(convert!("13:14")),
+        ok!(NaiveDate::from_ymd_opt(2000, 1, 1)).and_time(time)
+    );
+    assert_eq!(ok!(convert!("2460370.05138889")), date.and_time(time));
+    assert_eq!(ok!(convert!(0)), DateTime::UNIX_EPOCH.naive_utc());
+
+    assert!(convert!("2023-02-29").is_err());
+    assert!(convert!("2024-02-29 24:00").is_err());
+    assert!(convert!("2024-02-29 13:14 UTC").is_err());
+    assert!(convert!("2024-02-29 13:14:00.").is_err());
+    assert!(convert!("now").is_err());
+    assert!(convert!(-1.0).is_err());
+    assert!(convert!(vec![0u8]).is_err());
+    assert_eq!(ok!(Option::<NaiveDateTime>::try_from(&Value::Null)), None);
+}
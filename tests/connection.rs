@@ -1,4 +1,4 @@
-use sqlite::{Connection, OpenFlags, State};
+use sqlite::{Connection, DbStatus, OpenFlags, State};
 
 mod common;
 
@@ -62,6 +62,77 @@ fn open_thread_safe_sync() {
     }
 }
 
+#[test]
+fn attach() {
+    use std::path::Path;
+    use temporary::Folder;
+
+    let folder = ok!(Folder::new("sqlite"));
+    let path = folder.path().join("other database.sqlite3");
+    setup_users(&path);
+
+    let connection = ok!(sqlite::open(":memory:"));
+    let uri = unsafe { sqlite::ffi::sqlite3_compileoption_used(c"USE_URI".as_ptr()) != 0 };
+    for flags in [
+        OpenFlags::new().with_read_write(),
+        OpenFlags::new().with_read_only(),
+    ] {
+        match connection.attach(&path, "other", flags) {
+            Ok(_) if uri => ok!(connection.detach("other")),
+            Err(error) if !uri => assert_eq!(
+                error.message.as_deref(),
+                Some("the connection does not accept URIs"),
+            ),
+            result => panic!("unexpected result ({result:?})"),
+        }
+    }
+    assert!(connection.attach(&path, "main", OpenFlags::new()).is_err());
+    assert!(connection.attach(&path, "", OpenFlags::new()).is_err());
+
+    let flags = OpenFlags::new().with_create().with_read_write();
+    ok!(connection.attach(&path, "a \"quoted\" name", flags));
+    assert_eq!(
+        ok!(connection.database_names()),
+        ["main", "a \"quoted\" name"]
+    );
+    let (count,) = ok!(connection
+        .query_one::<(i64,), _, _>("SELECT count(*) FROM \"a \"\"quoted\"\" name\".users", (),));
+    assert_eq!(count, 1);
+    assert_eq!(ok!(connection.db_filename("main")), None);
+    assert_eq!(
+        ok!(connection.db_filename("a \"quoted\" name")),
+        Some(ok!(path.canonicalize()).to_str().unwrap().to_string()),
+    );
+    assert!(!ok!(connection.is_readonly("a \"quoted\" name")));
+    ok!(connection.detach("a \"quoted\" name"));
+    assert_eq!(ok!(connection.database_names()), ["main"]);
+    assert!(connection.db_filename("a \"quoted\" name").is_err());
+    assert!(connection.is_readonly("a \"quoted\" name").is_err());
+    assert!(connection.detach("a \"quoted\" name").is_err());
+
+    let flags = OpenFlags::new().with_create().with_read_write().with_uri();
+    let connection = ok!(Connection::open_with_flags(":memory:", flags));
+    ok!(connection.attach(&path, "other", OpenFlags::new().with_read_only()));
+    assert!(ok!(connection.is_readonly("other")));
+    assert!(connection.execute("DELETE FROM other.users").is_err());
+
+    let flags = OpenFlags::new().with_read_write();
+    for path in [":memory:", ""] {
+        ok!(connection.attach(path, "memory", flags));
+        assert_eq!(ok!(connection.db_filename("memory")), None);
+        ok!(connection.execute("CREATE TABLE memory.items (id INTEGER)"));
+        ok!(connection.detach("memory"));
+    }
+    assert!(!Path::new(":memory:").exists());
+
+    #[cfg(unix)]
+    {
+        let path = format!("/{}", path.display());
+        ok!(connection.attach(&path, "slashes", OpenFlags::new().with_read_only()));
+        assert!(ok!(connection.is_readonly("slashes")));
+    }
+}
+
 #[test]
 fn execute() {
     let connection = setup_users(":memory:");
@@ -170,3 +241,167 @@ fn change_count() {
     assert_eq!(connection.change_count(), 2);
     assert_eq!(connection.total_change_count(), 5);
 }
+
+#[test]
+fn status() {
+    let connection = setup_users(":memory:");
+    let (current, _) = ok!(connection.status(DbStatus::SchemaUsed, false));
+    assert!(current > 0);
+    let (current, highest) = ok!(connection.status(DbStatus::DeferredForeignKeys, false));
+    assert_eq!((current, highest), (0, 0));
+}
+
+#[test]
+fn query_all() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob', NULL, NULL, NULL)"));
+
+    let query = "SELECT id, name, age FROM users ORDER BY id";
+    let users = ok!(connection.query_all::<(i64, String, Option<f64>), _, _>(query, ()));
+    assert_eq!(
+        users,
+        vec![(1, "Alice".into(), Some(42.69)), (2, "Bob".into(), None)],
+    );
+
+    let query = "SELECT id FROM users WHERE id > ?";
+    let users = ok!(connection.query_all::<(i64,), _, _>(query, (1, 5)));
+    assert!(users.is_empty());
+}
+
+#[test]
+fn query_map() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob', NULL, NULL, NULL)"));
+
+    let query = "SELECT name FROM users WHERE id >= :id ORDER BY id";
+    let names = ok!(connection.query_map(query, (":id", 1), |statement| {
+        statement.read::<String, _>("name")
+    }));
+    assert_eq!(names, vec!["Alice", "Bob"]);
+
+    let query = "SELECT name FROM users";
+    let result = connection.query_map(query, (), |statement| statement.read::<String, _>("age"));
+    assert!(result.is_err());
+}
+
+#[test]
+fn query_one() {
+    let connection = setup_users(":memory:");
+
+    let query = "SELECT id, name, photo FROM users WHERE id = ?";
+    let (id, name, photo) =
+        ok!(connection.query_one::<(i64, String, Vec<u8>), _, _>(query, (1, 1)));
+    assert_eq!(id, 1);
+    assert_eq!(name, "Alice");
+    assert_eq!(photo, vec![0x42, 0x69]);
+}
+
+#[test]
+fn query_row() {
+    let connection = setup_users(":memory:");
+
+    let query = "SELECT count(*) FROM users";
+    let count = ok!(connection.query_row(query, (), |statement| statement.read::<i64, _>(0)));
+    assert_eq!(count, 1);
+
+    let query = "SELECT * FROM users WHERE id = 2";
+    match connection.query_row(query, (), |_| Ok(())) {
+        Err(error) => assert_eq!(error.to_string(), "the query returned no rows"),
+        _ => unreachable!(),
+    }
+
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob', NULL, NULL, NULL)"));
+    let query = "SELECT * FROM users";
+    match connection.query_row(query, (), |_| Ok(())) {
+        Err(error) => assert_eq!(error.to_string(), "the query returned more than one row"),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn bulk_insert() {
+    use sqlite::Value;
+
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("CREATE UNIQUE INDEX users_id ON users (id)"));
+
+    let columns = ["id", "name", "age"];
+    let mut inserter = ok!(connection.bulk_insert("users", &columns)).with_batch_size(2);
+    ok!(inserter.insert((2, "Bob", 69.42)));
+    ok!(inserter.insert(&[Value::Integer(3), "Carol".into(), Value::Null][..]));
+    assert_eq!(count(&connection), 3);
+    ok!(inserter.insert((4, "Dave", 42.0)));
+    assert!(inserter.insert((1, "Eve", 42.0)).is_err());
+    ok!(inserter.insert((6, "Frank", 42.0)));
+    assert!(inserter.insert((2, "Frank", 42.0)).is_err());
+    ok!(inserter.insert((5, "Grace", 42.0)));
+    assert_eq!(inserter.inserted(), 5);
+    let (index, error) = inserter.failure().unwrap();
+    assert_eq!(index, 3);
+    assert!(error
+        .message
+        .as_ref()
+        .unwrap()
+        .starts_with("failed to insert row 3"));
+    assert_eq!(ok!(inserter.finish()), 5);
+    assert_eq!(count(&connection), 6);
+
+    let mut inserter = ok!(connection.bulk_insert("users", &columns));
+    ok!(inserter.insert((7, "Heidi", 42.0)));
+    drop(inserter);
+    assert_eq!(count(&connection), 6);
+
+    ok!(connection.execute("BEGIN"));
+    let mut inserter = ok!(connection.bulk_insert("users", &columns)).with_batch_size(1);
+    ok!(inserter.insert((7, "Heidi", 42.0)));
+    assert_eq!(ok!(inserter.finish()), 1);
+    ok!(connection.execute("ROLLBACK"));
+    assert_eq!(count(&connection), 6);
+
+    assert!(connection.bulk_insert::<&str>("users", &[]).is_err());
+
+    let mut inserter = ok!(connection.bulk_insert("main.users", &columns));
+    ok!(inserter.insert((7, "Heidi", 42.0)));
+    assert_eq!(ok!(inserter.finish()), 1);
+    assert_eq!(count(&connection), 7);
+
+    fn count(connection: &Connection) -> i64 {
+        ok!(connection.query_one::<(i64,), _, _>("SELECT COUNT(*) FROM users", ())).0
+    }
+}
+
+#[test]
+fn bulk_insert_failure() {
+    let connection = ok!(sqlite::open(":memory:"));
+    ok!(connection.execute(
+        "
+        PRAGMA foreign_keys = ON;
+        CREATE TABLE users (id INTEGER PRIMARY KEY);
+        CREATE TABLE posts (
+            id INTEGER PRIMARY KEY,
+            user_id INTEGER REFERENCES users (id) DEFERRABLE INITIALLY DEFERRED
+        );
+        ",
+    ));
+
+    let mut inserter = ok!(connection.bulk_insert("users", &["id"]));
+    ok!(inserter.insert((1,)));
+    assert!(inserter.insert((1,)).is_err());
+    ok!(inserter.insert((2,)));
+    assert!(inserter.insert(("a",)).is_err());
+    ok!(inserter.insert((3,)));
+    assert_eq!(ok!(inserter.finish()), 3);
+    assert_eq!(count(&connection), 3);
+
+    let mut inserter = ok!(connection.bulk_insert("posts", &["id", "user_id"]));
+    ok!(inserter.insert((1, 1)));
+    ok!(inserter.insert((2, 42)));
+    assert!(inserter.finish().is_err());
+    let query = "SELECT COUNT(*) FROM posts";
+    assert_eq!(ok!(connection.query_one::<(i64,), _, _>(query, ())).0, 0);
+    ok!(connection.execute("INSERT INTO posts VALUES (3, 1)"));
+
+    fn count(connection: &Connection) -> i64 {
+        ok!(connection.query_one::<(i64,), _, _>("SELECT COUNT(*) FROM users", ())).0
+    }
+}
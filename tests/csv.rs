@@ -0,0 +1,134 @@
+#![cfg(feature = "csv")]
+
+use sqlite::{BlobEncoding, Connection, CsvExportOptions, CsvImportOptions, Value};
+
+mod common;
+
+use common::setup_users;
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+#[test]
+fn export() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob, Jr.', 1.0, X'00FF7E', NULL)"));
+    let query = "SELECT * FROM users ORDER BY id";
+
+    let mut buffer = Vec::new();
+    let options = CsvExportOptions::new();
+    assert_eq!(ok!(connection.export_csv(query, &mut buffer, &options)), 2);
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "id,name,age,photo,email\n1,Alice,42.69,4269,\n2,\"Bob, Jr.\",1.0,00ff7e,\n",
+    );
+
+    let mut buffer = Vec::new();
+    let options = CsvExportOptions::new()
+        .with_delimiter(b';')
+        .without_header()
+        .with_null("\\N")
+        .with_blob_encoding(BlobEncoding::Base64);
+    ok!(connection.export_csv(query, &mut buffer, &options));
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "1;Alice;42.69;Qmk=;\\N\n2;Bob, Jr.;1.0;AP9+;\\N\n",
+    );
+
+    let mut buffer = Vec::new();
+    let options = CsvExportOptions::new().with_blob_encoding(BlobEncoding::Text);
+    ok!(connection.export_csv(
+        "SELECT photo FROM users WHERE id = 1",
+        &mut buffer,
+        &options
+    ));
+    assert_eq!(buffer, b"photo\nBi\n");
+}
+
+#[test]
+fn import() {
+    let connection = setup_users(":memory:");
+    let data = "\
+        id,name,age,photo,email\n\
+        2,Bob,69.42,,\n\
+        3,Carol\n\
+        x,Dave,,,dave@example.com\n\
+        5,\"Eve, Sr.\",,,\n";
+    ok!(connection.execute(
+        "
+        CREATE TRIGGER check_id BEFORE INSERT ON users WHEN NEW.id = 'x' BEGIN
+            SELECT RAISE(ABORT, 'invalid id');
+        END
+        ",
+    ));
+    let summary = ok!(connection.import_csv(data.as_bytes(), "users", &CsvImportOptions::new()));
+    assert_eq!(summary.inserted(), 2);
+    let lines = summary
+        .errors()
+        .iter()
+        .map(|(line, _)| *line)
+        .collect::<Vec<_>>();
+    assert_eq!(lines, [3, 4]);
+    assert!(ok!(summary.errors()[1].1.message.as_ref()).contains("invalid id"));
+
+    let query = "SELECT id, name, age FROM users ORDER BY id";
+    let rows = ok!(connection.query_all::<(i64, String, Option<f64>), _, _>(query, ()));
+    assert_eq!(
+        rows,
+        [
+            (1, "Alice".into(), Some(42.69)),
+            (2, "Bob".into(), Some(69.42)),
+            (5, "Eve, Sr.".into(), None),
+        ],
+    );
+
+    let data = "6;Frank;;;\n";
+    let options = CsvImportOptions::new()
+        .with_delimiter(b';')
+        .without_header();
+    let summary = ok!(connection.import_csv(data.as_bytes(), "users", &options));
+    assert_eq!(summary.inserted(), 1);
+}
+
+#[test]
+fn import_with_create() {
+    let connection = ok!(Connection::open(":memory:"));
+    let data = "1|1.5|a|\n2|2|b|NULL\n3|x|c|\n";
+    let options = CsvImportOptions::new()
+        .without_header()
+        .with_delimiter(b'|')
+        .with_null("NULL")
+        .with_create()
+        .with_inference_size(2)
+        .with_batch_size(1);
+    let summary = ok!(connection.import_csv(data.as_bytes(), "data", &options));
+    assert_eq!(summary.inserted(), 3);
+    assert!(summary.errors().is_empty());
+
+    let query = "SELECT name, type FROM pragma_table_info('data')";
+    let columns = ok!(connection.query_all::<(String, String), _, _>(query, ()));
+    assert_eq!(
+        columns,
+        [
+            ("c1".into(), "INTEGER".into()),
+            ("c2".into(), "REAL".into()),
+            ("c3".into(), "TEXT".into()),
+            ("c4".into(), "TEXT".into()),
+        ],
+    );
+    let query = "SELECT c2, c4 FROM data ORDER BY c1";
+    let rows = ok!(connection.query_all::<(Value, Value), _, _>(query, ()));
+    assert_eq!(
+        rows,
+        [
+            (Value::Float(1.5), Value::String("".into())),
+            (Value::Float(2.0), Value::Null),
+            (Value::String("x".into()), Value::String("".into())),
+        ],
+    );
+
+    let summary = ok!(connection.import_csv(data.as_bytes(), "temp.data", &options));
+    assert_eq!(summary.inserted(), 3);
+    let query = "SELECT COUNT(*) FROM temp.data";
+    let count = ok!(connection.query_one::<(i64,), _, _>(query, ()));
+    assert_eq!(count.0, 3);
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use sqlite::{Type, Value};
+use sqlite::{Type, Value, ValueRef};
 
 mod common;
 
@@ -134,3 +134,31 @@ fn workflow() {
     assert_eq!(row.read::<&str, _>("name"), "Bob");
     assert!(select.next().is_none());
 }
+
+#[test]
+fn rows_ref() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob', NULL, NULL, NULL)"));
+    let query = "SELECT * FROM users WHERE id >= ? ORDER BY id";
+    let mut statement = ok!(connection.prepare(query));
+
+    let mut rows = ok!(statement.rows_ref().bind((1, 1)));
+    let row = ok!(rows.next()).unwrap();
+    assert_eq!(row.column_count(), 5);
+    assert_eq!(ok!(row.column_name(1)), "name");
+    assert_eq!(ok!(row.read_ref(0)), ValueRef::Integer(1));
+    assert_eq!(ok!(row.read_ref("name")), ValueRef::Text("Alice"));
+    assert_eq!(ok!(row.read_ref(2)), ValueRef::Real(42.69));
+    assert_eq!(ok!(row.read_ref("photo")), ValueRef::Blob(&[0x42, 0x69]));
+    assert_eq!(ok!(row.read_ref(4)), ValueRef::Null);
+    assert_eq!(ok!(row.read::<String, _>("photo")), "\x42\x69");
+    assert_eq!(ok!(row.read::<Option<String>, _>("email")), None);
+    let row = ok!(rows.next()).unwrap();
+    let name: &str = ok!(ok!(row.read_ref("name")).try_into());
+    assert_eq!(name, "Bob");
+    assert!(ok!(rows.next()).is_none());
+
+    let mut rows = ok!(statement.rows_ref().bind((1, 2)));
+    let row = ok!(rows.next()).unwrap();
+    assert_eq!(ok!(row.read::<i64, _>("id")), 2);
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "derive")]
+
+use sqlite::{Bindable, FromRow, State};
+
+mod common;
+
+use common::setup_users;
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+#[derive(Bindable, Debug, FromRow, PartialEq)]
+struct User {
+    id: i64,
+    #[sqlite(rename = "name")]
+    first_name: String,
+    age: Option<f64>,
+    #[sqlite(flatten)]
+    contact: Contact,
+    #[sqlite(default)]
+    nickname: Option<String>,
+    #[sqlite(skip)]
+    cache: Vec<u8>,
+}
+
+#[derive(Bindable, Debug, FromRow, PartialEq)]
+struct Contact {
+    email: Option<String>,
+}
+
+#[derive(Debug, FromRow, PartialEq)]
+struct Pair(i64, String);
+
+#[test]
+fn bindable() {
+    let connection = setup_users(":memory:");
+    let query = "INSERT INTO users (id, name, age, email) VALUES (:id, :name, :age, :email)";
+    let mut statement = ok!(connection.prepare(query));
+    ok!(statement.bind(User {
+        id: 2,
+        first_name: "Bob".into(),
+        age: None,
+        contact: Contact {
+            email: Some("bob@example.com".into()),
+        },
+        nickname: Some("Bobby".into()),
+        cache: vec![],
+    }));
+    assert_eq!(ok!(statement.next()), State::Done);
+
+    let query = "SELECT email FROM users WHERE id = 2";
+    let (email,) = ok!(connection.query_one::<(String,), _, _>(query, ()));
+    assert_eq!(email, "bob@example.com");
+}
+
+#[test]
+fn from_row() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("UPDATE users SET email = 'alice@example.com'"));
+
+    let query = "SELECT * FROM users";
+    let user = ok!(connection.query_one::<User, _, _>(query, ()));
+    assert_eq!(
+        user,
+        User {
+            id: 1,
+            first_name: "Alice".into(),
+            age: Some(42.69),
+            contact: Contact {
+                email: Some("alice@example.com".into()),
+            },
+            nickname: None,
+            cache: vec![],
+        },
+    );
+
+    let query = "SELECT id, name FROM users";
+    let pair = ok!(connection.query_one::<Pair, _, _>(query, ()));
+    assert_eq!(pair, Pair(1, "Alice".into()));
+
+    let query = "SELECT id FROM users";
+    assert!(connection.query_one::<User, _, _>(query, ()).is_err());
+}
@@ -0,0 +1,94 @@
+use std::thread;
+
+use sqlite::{Connection, MemoryVfs, OpenFlags};
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+#[test]
+fn share() {
+    let vfs = MemoryVfs::new();
+    ok!(sqlite::register_vfs("share", vfs.clone(), false));
+    let writer = open("database", "share");
+    let reader = open("database", "share");
+    ok!(writer.execute("CREATE TABLE users (id INTEGER, name TEXT)"));
+    ok!(writer.execute("INSERT INTO users VALUES (1, 'Alice')"));
+    assert_eq!(count(&reader), 1);
+
+    ok!(writer.execute("BEGIN EXCLUSIVE"));
+    ok!(writer.execute("INSERT INTO users VALUES (2, 'Bob')"));
+    assert!(reader.execute("SELECT * FROM users").is_err());
+    ok!(writer.execute("COMMIT"));
+    assert_eq!(count(&reader), 2);
+
+    let other = open("other", "share");
+    assert!(other.execute("SELECT * FROM users").is_err());
+
+    ok!(writer.execute("CREATE TEMP TABLE names AS SELECT name FROM users"));
+    let query = "SELECT count(*) FROM temp.names";
+    assert_eq!(ok!(writer.query_one::<(i64,), _, _>(query, ())).0, 2);
+}
+
+#[test]
+fn snapshot() {
+    let vfs = MemoryVfs::new();
+    ok!(sqlite::register_vfs("snapshot", vfs.clone(), false));
+    let connection = open("database", "snapshot");
+    ok!(connection.execute("CREATE TABLE users (id INTEGER, name TEXT)"));
+    ok!(connection.execute("INSERT INTO users VALUES (1, 'Alice')"));
+    let data = ok!(vfs.snapshot("database"));
+    assert!(vfs.snapshot("missing").is_err());
+
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob')"));
+    drop(connection);
+    ok!(vfs.restore("database", &data));
+    let connection = open("database", "snapshot");
+    assert_eq!(count(&connection), 1);
+}
+
+#[test]
+fn wal() {
+    ok!(sqlite::register_vfs("wal", MemoryVfs::new(), false));
+    let connection = open("database", "wal");
+    let query = "PRAGMA journal_mode = WAL";
+    let (mode,) = ok!(connection.query_one::<(String,), _, _>(query, ()));
+    assert_eq!(mode, "wal");
+    ok!(connection.execute("CREATE TABLE users (id INTEGER, name TEXT)"));
+
+    let writer = thread::spawn(|| {
+        let mut connection = open("database", "wal");
+        ok!(connection.set_busy_timeout(5000));
+        for id in 0..100 {
+            let query = format!("INSERT INTO users VALUES ({id}, 'Alice')");
+            ok!(connection.execute(query));
+        }
+    });
+    let readers = (0..4)
+        .map(|_| {
+            thread::spawn(|| {
+                let mut connection = open("database", "wal");
+                ok!(connection.set_busy_timeout(5000));
+                let mut last = 0;
+                while last < 100 {
+                    let current = count(&connection);
+                    assert!(current >= last);
+                    last = current;
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    ok!(writer.join());
+    for reader in readers {
+        ok!(reader.join());
+    }
+    assert_eq!(count(&connection), 100);
+}
+
+fn count(connection: &Connection) -> i64 {
+    let query = "SELECT count(*) FROM users";
+    ok!(connection.query_one::<(i64,), _, _>(query, ())).0
+}
+
+fn open(path: &str, vfs: &str) -> Connection {
+    let flags = OpenFlags::new().with_create().with_read_write();
+    ok!(Connection::open_with_flags_and_vfs(path, flags, vfs))
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::setup_users;
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+#[test]
+fn display() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("CREATE INDEX users_name ON users (name)"));
+    let query = "
+        SELECT name FROM users WHERE name = 'Alice'
+        UNION
+        SELECT name FROM users WHERE age > 18 ORDER BY 1
+    ";
+    let plan = ok!(connection.query_plan(query));
+    assert_eq!(
+        plan.to_string(),
+        "\
+QUERY PLAN
+`--MERGE (UNION)
+   |--LEFT
+   |  `--SEARCH users USING COVERING INDEX users_name (name=?)
+   `--RIGHT
+      `--SCAN users USING INDEX users_name
+"
+    );
+}
+
+#[test]
+fn nodes() {
+    let connection = setup_users(":memory:");
+    let plan = ok!(connection.query_plan("SELECT * FROM users"));
+    assert_eq!(plan.nodes().len(), 1);
+    let node = &plan.nodes()[0];
+    assert_eq!(node.parent, 0);
+    assert_eq!(node.detail, "SCAN users");
+    assert_eq!(plan.roots().count(), 1);
+    assert_eq!(plan.children(node.id).count(), 0);
+}
+
+#[test]
+fn uses_full_scan() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("CREATE INDEX users_id ON users (id)"));
+
+    let plan = ok!(connection.query_plan("SELECT * FROM users WHERE name = 'Alice'"));
+    assert!(plan.uses_full_scan());
+
+    let plan = ok!(connection.query_plan("SELECT * FROM users WHERE id = 1"));
+    assert!(!plan.uses_full_scan());
+
+    let plan = ok!(connection.query_plan("SELECT 1"));
+    assert!(!plan.uses_full_scan());
+}
+
+#[test]
+fn uses_index() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("CREATE INDEX users_id ON users (id)"));
+    ok!(connection.execute("CREATE INDEX users_id_name ON users (id, name)"));
+
+    let plan = ok!(connection.query_plan("SELECT * FROM users WHERE id = 1"));
+    assert!(plan.uses_index("users_id"));
+    assert!(!plan.uses_index("users_id_name"));
+
+    let plan = ok!(connection.query_plan("SELECT name FROM users WHERE id = 1"));
+    assert!(plan.uses_index("users_id_name"));
+    assert!(!plan.uses_index("users_id"));
+}
+
+#[test]
+fn uses_temp_btree() {
+    let connection = setup_users(":memory:");
+
+    let plan = ok!(connection.query_plan("SELECT * FROM users ORDER BY name"));
+    assert!(plan.uses_temp_btree());
+
+    let plan = ok!(connection.query_plan("SELECT * FROM users"));
+    assert!(!plan.uses_temp_btree());
+}
@@ -1,4 +1,7 @@
-use sqlite::Value;
+use std::borrow::Cow;
+use std::rc::Rc;
+
+use sqlite::{ErrorKind, State, Value};
 
 mod common;
 
@@ -104,6 +107,56 @@ fn try_read_with_index() {
     assert!(row.try_read::<&str, _>(4).is_err());
 }
 
+#[test]
+fn try_read_with_conversion() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("UPDATE users SET id = 300, email = 'a'"));
+    let query = "SELECT * FROM users";
+    let mut statement = ok!(connection.prepare(query));
+
+    let row = ok!(ok!(statement.iter().next()));
+    assert_eq!(ok!(row.try_read::<i16, _>("id")), 300);
+    assert_eq!(ok!(row.try_read::<u32, _>("id")), 300);
+    assert_eq!(ok!(row.try_read::<usize, _>("id")), 300);
+    assert!(ok!(row.try_read::<bool, _>("id")));
+    assert_eq!(ok!(row.try_read::<f32, _>("age")), 42.69);
+    assert_eq!(ok!(row.try_read::<char, _>("email")), 'a');
+    assert_eq!(ok!(row.try_read::<Cow<str>, _>("name")), "Alice");
+    assert_eq!(&*ok!(row.try_read::<Rc<str>, _>("name")), "Alice");
+    assert_eq!(ok!(row.try_read::<[u8; 2], _>("photo")), [0x42, 0x69]);
+    assert_eq!(ok!(row.try_read::<Option<i16>, _>("id")), Some(300));
+    assert!(row.try_read::<i16, _>("age").is_err());
+
+    match row.try_read::<u8, _>("id") {
+        Err(error) => assert_eq!(error.kind, ErrorKind::OutOfRange),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn try_read_with_conversion_out_of_range() {
+    let connection = ok!(sqlite::open(":memory:"));
+    let query = "SELECT 1e300, -1e300, 1e308 * 10";
+    let mut statement = ok!(connection.prepare(query));
+
+    let row = ok!(ok!(statement.iter().next()));
+    for index in 0..2 {
+        match row.try_read::<f32, _>(index) {
+            Err(error) => assert_eq!(error.kind, ErrorKind::OutOfRange),
+            _ => unreachable!(),
+        }
+    }
+    assert_eq!(ok!(row.try_read::<f32, _>(2)), f32::INFINITY);
+
+    let mut statement = ok!(connection.prepare(query));
+    assert_eq!(ok!(statement.next()), State::Row);
+    match statement.read::<f32, _>(0) {
+        Err(error) => assert_eq!(error.kind, ErrorKind::OutOfRange),
+        _ => unreachable!(),
+    }
+    assert_eq!(ok!(statement.read::<f32, _>(2)), f32::INFINITY);
+}
+
 #[test]
 fn try_read_with_index_out_of_range() {
     let connection = setup_users(":memory:");
@@ -0,0 +1,46 @@
+#![cfg(feature = "rust_decimal")]
+
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use sqlite::{Connection, State, Value};
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+#[test]
+fn bind() {
+    let connection = ok!(Connection::open(":memory:"));
+    ok!(connection.execute("CREATE TABLE prices (value TEXT)"));
+    let value = ok!(Decimal::from_str("12345678901234567890.123456789"));
+    let mut statement = ok!(connection.prepare("INSERT INTO prices VALUES (?)"));
+    ok!(statement.bind((1, value)));
+    assert_eq!(ok!(statement.next()), State::Done);
+
+    let mut statement = ok!(connection.prepare("SELECT value FROM prices"));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(
+        ok!(statement.read::<String, _>(0)),
+        "12345678901234567890.123456789"
+    );
+    assert_eq!(ok!(statement.read::<Decimal, _>(0)), value);
+}
+
+#[test]
+fn read() {
+    let connection = ok!(Connection::open(":memory:"));
+    let mut statement = ok!(connection.prepare("SELECT '0.10', 42, 0.5, 'one', NULL"));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(ok!(statement.read::<Decimal, _>(0)), Decimal::new(10, 2));
+    assert_eq!(ok!(statement.read::<Decimal, _>(1)), Decimal::from(42));
+    assert_eq!(ok!(statement.read::<Decimal, _>(2)), Decimal::new(5, 1));
+    assert!(statement.read::<Decimal, _>(3).is_err());
+    assert_eq!(ok!(statement.read::<Option<Decimal>, _>(4)), None);
+}
+
+#[test]
+fn value() {
+    let value = Decimal::new(-1050, 2);
+    assert_eq!(Value::from(value), Value::String("-10.50".into()));
+    assert_eq!(ok!(Decimal::try_from(&Value::from(value))), value);
+    assert!(Decimal::try_from(&Value::Float(f64::NAN)).is_err());
+}
@@ -0,0 +1,147 @@
+#![cfg(feature = "serde")]
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use sqlite::{Serialized, State, Value};
+
+mod common;
+
+use common::setup_users;
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct User {
+    id: i64,
+    name: String,
+    age: Option<f64>,
+    #[serde(with = "bytes")]
+    photo: Option<Vec<u8>>,
+    email: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Kind {
+    Admin,
+    Guest,
+}
+
+mod bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<Vec<u8>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_bytes(value),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'l, D: Deserializer<'l>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<u8>>, D::Error> {
+        Option::<&[u8]>::deserialize(deserializer).map(|value| value.map(<[u8]>::to_vec))
+    }
+}
+
+#[test]
+fn bind() {
+    let connection = setup_users(":memory:");
+    let query = "INSERT INTO users VALUES (:id, :name, :age, :photo, :email)";
+    let mut statement = ok!(connection.prepare(query));
+    ok!(statement.bind(Serialized(User {
+        id: 2,
+        name: "Bob".into(),
+        age: None,
+        photo: Some(vec![0x69, 0x42]),
+        email: Some("bob@example.com".into()),
+    })));
+    assert_eq!(ok!(statement.next()), State::Done);
+
+    let query = "SELECT * FROM users WHERE id = ?";
+    let mut statement = ok!(connection.prepare(query));
+    ok!(statement.bind(Serialized((2,))));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(ok!(statement.read::<String, _>("name")), "Bob");
+    assert_eq!(ok!(statement.read::<Vec<u8>, _>("photo")), vec![0x69, 0x42]);
+
+    let query = "SELECT * FROM users WHERE name = :name AND id = $id";
+    let mut statement = ok!(connection.prepare(query));
+    let mut parameters = HashMap::new();
+    parameters.insert("name", Value::from("Bob"));
+    parameters.insert("$id", Value::from(2));
+    ok!(statement.bind(Serialized(&parameters)));
+    assert_eq!(ok!(statement.next()), State::Row);
+
+    let query = "SELECT * FROM users WHERE name = ?";
+    let mut statement = ok!(connection.prepare(query));
+    ok!(statement.bind(Serialized([Kind::Admin])));
+    assert_eq!(ok!(statement.next()), State::Done);
+    assert!(statement.bind(Serialized(42)).is_err());
+    assert!(statement.bind(Serialized([[42]])).is_err());
+}
+
+#[test]
+fn deserialize_row() {
+    let connection = setup_users(":memory:");
+    let query = "SELECT * FROM users";
+    let mut statement = ok!(connection.prepare(query));
+    let row = ok!(ok!(statement.iter().next()));
+
+    assert_eq!(
+        ok!(row.deserialize::<User>()),
+        User {
+            id: 1,
+            name: "Alice".into(),
+            age: Some(42.69),
+            photo: Some(vec![0x42, 0x69]),
+            email: None,
+        },
+    );
+
+    let (id, name) = ok!(row.deserialize::<(i64, &str)>());
+    assert_eq!((id, name), (1, "Alice"));
+
+    let map = ok!(row.deserialize::<HashMap<String, Value>>());
+    assert_eq!(map.len(), 5);
+    assert_eq!(map["age"], Value::Float(42.69));
+    assert_eq!(map["email"], Value::Null);
+
+    assert!(row.deserialize::<i64>().is_err());
+
+    ok!(statement.reset());
+    ok!(statement.for_each_row(|row| {
+        assert_eq!(row.deserialize::<(i64, &str)>()?, (1, "Alice"));
+        Ok(())
+    }));
+}
+
+#[test]
+fn deserialize_statement() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("UPDATE users SET email = 'admin'"));
+
+    let query = "SELECT email FROM users";
+    let mut statement = ok!(connection.prepare(query));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(ok!(statement.deserialize::<Kind>()), Kind::Admin);
+    assert_eq!(ok!(statement.deserialize::<String>()), "admin");
+
+    let query = "SELECT id, id > 0 AS positive FROM users";
+    let mut statement = ok!(connection.prepare(query));
+    assert_eq!(ok!(statement.next()), State::Row);
+
+    #[derive(Deserialize)]
+    struct Row {
+        id: u8,
+        positive: bool,
+    }
+
+    let row = ok!(statement.deserialize::<Row>());
+    assert_eq!(row.id, 1);
+    assert!(row.positive);
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "serde_json")]
+
+use serde_json::{json, Value as Json};
+use sqlite::{Connection, State, Value};
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+#[test]
+fn bind() {
+    let connection = ok!(Connection::open(":memory:"));
+    let value = json!({"name": "Alice", "tags": [1, 2.5, null, true]});
+    let mut statement = ok!(connection.prepare("SELECT ?, json_extract(?1, '$.tags[1]')"));
+    ok!(statement.bind((1, &value)));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(
+        ok!(statement.read::<String, _>(0)),
+        r#"{"name":"Alice","tags":[1,2.5,null,true]}"#
+    );
+    assert_eq!(ok!(statement.read::<Json, _>(0)), value);
+    assert_eq!(ok!(statement.read::<Json, _>(1)), json!(2.5));
+}
+
+#[test]
+fn read() {
+    let connection = ok!(Connection::open(":memory:"));
+    let query = "SELECT json_object('a', json_array(1, 'x')), 42, NULL, 'nope'";
+    let mut statement = ok!(connection.prepare(query));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(ok!(statement.read::<Json, _>(0)), json!({"a": [1, "x"]}));
+    assert_eq!(ok!(statement.read::<Json, _>(1)), json!(42));
+    assert_eq!(ok!(statement.read::<Json, _>(2)), Json::Null);
+    assert_eq!(ok!(statement.read::<Option<Json>, _>(2)), None);
+    assert!(statement.read::<Json, _>(3).is_err());
+}
+
+#[test]
+fn read_extracted() {
+    let connection = ok!(Connection::open(":memory:"));
+    let query = "SELECT json_extract(?1, '$.name'), json_extract(?1, '$.age')";
+    let mut statement = ok!(connection.prepare(query));
+    ok!(statement.bind((1, r#"{"name": "Alice", "age": 42}"#)));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert!(statement.read::<Json, _>(0).is_err());
+    assert_eq!(ok!(statement.read::<String, _>(0)), "Alice");
+    assert_eq!(ok!(statement.read::<Json, _>(1)), json!(42));
+
+    if sqlite::version() >= 3_038_000 {
+        let query = "SELECT ? -> '$.name'";
+        let mut statement = ok!(connection.prepare(query));
+        ok!(statement.bind((1, r#"{"name": "Alice"}"#)));
+        assert_eq!(ok!(statement.next()), State::Row);
+        assert_eq!(ok!(statement.read::<Json, _>(0)), json!("Alice"));
+    }
+}
+
+#[test]
+fn read_jsonb() {
+    macro_rules! convert(
+        ($bytes:expr) => (Json::try_from(&Value::Binary($bytes.to_vec())));
+    );
+
+    assert_eq!(
+        ok!(convert!(b"\x9c\x17a\x6b\x131\x01\x00\x17x")),
+        json!({"a": [1, true, null, "x"]})
+    );
+    assert_eq!(ok!(convert!(b"\x54-0x1F")), json!(-31));
+    assert_eq!(ok!(convert!(b"\x56+.5e1")), json!(5.0));
+    assert_eq!(ok!(convert!(b"\x68\\u0041")), json!("A"));
+    assert_eq!(ok!(convert!(b"\x99\\x41\\'\\\n\"")), json!("A'\""));
+    assert_eq!(
+        ok!(convert!(b"\xcb\x0f\xca\x0cxxxxxxxxxxxx\x02")),
+        json!(["x".repeat(12), false])
+    );
+    assert!(convert!(b"\x34--5").is_err());
+    assert!(convert!(b"\x34+-5").is_err());
+    assert!(convert!(b"\x54-0x-5").is_err());
+    assert!(convert!(b"\x13").is_err());
+    assert!(convert!(b"\x00\x00").is_err());
+    assert!(convert!(b"\x0d").is_err());
+    assert!(convert!(b"\xfb\xff\xff\xff\xff\xff\xff\xff\xff").is_err());
+    assert_eq!(
+        ok!(convert!(nest(100))).to_string(),
+        "[".repeat(101) + &"]".repeat(101)
+    );
+    assert!(convert!(nest(10000)).is_err());
+
+    if sqlite::version() >= 3_045_000 {
+        let connection = ok!(Connection::open(":memory:"));
+        let query = "SELECT jsonb('{\"a\": [1, 0x10, .5, ''x'', \"\\u0041\"]}')";
+        let mut statement = ok!(connection.prepare(query));
+        assert_eq!(ok!(statement.next()), State::Row);
+        assert_eq!(
+            ok!(statement.read::<Json, _>(0)),
+            json!({"a": [1, 16, 0.5, "x", "A"]})
+        );
+    }
+}
+
+#[test]
+fn value() {
+    let value = json!([1, "two"]);
+    assert_eq!(
+        Value::from(value.clone()),
+        Value::String(r#"[1,"two"]"#.into())
+    );
+    assert_eq!(ok!(Json::try_from(&Value::from(value.clone()))), value);
+}
+
+fn nest(depth: usize) -> Vec<u8> {
+    let mut bytes = vec![0x0b];
+    for _ in 0..depth {
+        let size = bytes.len() as u32;
+        let mut header = vec![0xeb];
+        header.extend(size.to_be_bytes());
+        bytes.splice(0..0, header);
+    }
+    bytes
+}
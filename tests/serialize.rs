@@ -0,0 +1,67 @@
+#![cfg(feature = "serialize")]
+
+use sqlite::DeserializeMode;
+
+mod common;
+
+use common::setup_users;
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+#[test]
+fn deserialize() {
+    let connection = setup_users(":memory:");
+    let data = ok!(connection.serialize("main"));
+
+    let connection = ok!(sqlite::open(":memory:"));
+    ok!(connection.deserialize("main", &*data, DeserializeMode::Resizeable));
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob', NULL, NULL, NULL)"));
+    ok!(connection
+        .execute("INSERT INTO users SELECT id + 2, zeroblob(10000), NULL, NULL, NULL FROM users"));
+    let modified = ok!(connection.serialize("main"));
+    assert!(modified.len() > data.len());
+
+    let connection = ok!(sqlite::open(":memory:"));
+    ok!(connection.execute("ATTACH ':memory:' AS other"));
+    ok!(connection.deserialize("other", &*modified, DeserializeMode::ReadOnly));
+    let query = "SELECT count(*) FROM other.users";
+    assert_eq!(ok!(connection.query_one::<(i64,), _, _>(query, ())).0, 4);
+    assert!(connection.execute("DELETE FROM other.users").is_err());
+    assert!(connection
+        .deserialize("missing", &*data, DeserializeMode::ReadOnly)
+        .is_err());
+}
+
+#[test]
+fn serialize() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("ATTACH ':memory:' AS other"));
+    ok!(connection.execute("CREATE TABLE other.items (id INTEGER)"));
+
+    let main = ok!(connection.serialize("main"));
+    let other = ok!(connection.serialize("other"));
+    assert!(main.starts_with(b"SQLite format 3\0"));
+    assert!(other.starts_with(b"SQLite format 3\0"));
+    assert_ne!(&main[..], &other[..]);
+    assert!(connection.serialize("missing").is_err());
+
+    // An empty database is serialized either as no data or as a single page.
+    let data = ok!(ok!(sqlite::open(":memory:")).serialize("main"));
+    let connection = ok!(sqlite::open(":memory:"));
+    ok!(connection.deserialize("main", &*data, DeserializeMode::ReadOnly));
+    let query = "SELECT count(*) FROM sqlite_schema";
+    assert_eq!(ok!(connection.query_one::<(i64,), _, _>(query, ())).0, 0);
+}
+
+#[test]
+fn serialize_no_copy() {
+    let mut connection = setup_users(":memory:");
+    assert!(ok!(connection.serialize_no_copy("main")).is_none());
+    assert!(connection.serialize_no_copy("missing").is_err());
+
+    let data = ok!(connection.serialize("main")).to_vec();
+    let mut connection = ok!(sqlite::open(":memory:"));
+    ok!(connection.deserialize("main", data.clone(), DeserializeMode::ReadOnly));
+    let borrowed = ok!(ok!(connection.serialize_no_copy("main")));
+    assert_eq!(borrowed, &data[..]);
+}
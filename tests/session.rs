@@ -0,0 +1,217 @@
+#![cfg(feature = "session")]
+
+use sqlite::{
+    Change, Changeset, ConflictAction, ConflictType, Connection, Operation, Session, Value,
+};
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+#[test]
+fn apply_changeset() {
+    let source = setup();
+    let target = setup();
+    ok!(target.execute("INSERT INTO users VALUES (1, 'Alice', 42)"));
+    ok!(target.execute("INSERT INTO users VALUES (2, 'Robert', 69)"));
+
+    let mut session = ok!(Session::new(&source, "main"));
+    ok!(session.attach_all());
+    ok!(source.execute("INSERT INTO users VALUES (1, 'Alice', 42)"));
+    ok!(source.execute("INSERT INTO users VALUES (2, 'Bob', 69)"));
+    ok!(source.execute("INSERT INTO users VALUES (3, 'Carol', 7)"));
+    ok!(source.execute("INSERT INTO items VALUES (1)"));
+    let changeset = ok!(session.changeset());
+
+    let mut conflicts = Vec::new();
+    ok!(target.apply_changeset(
+        &changeset,
+        |table| table == "users",
+        |kind, change| {
+            let change = change.unwrap();
+            conflicts.push((kind, change.new[0].clone()));
+            match change.new[1] {
+                Some(Value::String(ref name)) if name == "Bob" => ConflictAction::Replace,
+                _ => ConflictAction::Omit,
+            }
+        },
+    ));
+    assert_eq!(
+        conflicts,
+        [
+            (ConflictType::Conflict, Some(Value::Integer(1))),
+            (ConflictType::Conflict, Some(Value::Integer(2))),
+        ],
+    );
+    let query = "SELECT name FROM users ORDER BY id";
+    let names = ok!(target.query_all::<(String,), _, _>(query, ()));
+    assert_eq!(
+        names,
+        [("Alice".into(),), ("Bob".into(),), ("Carol".into(),)]
+    );
+    let count = ok!(target.query_one::<(i64,), _, _>("SELECT COUNT(*) FROM items", ()));
+    assert_eq!(count.0, 0);
+
+    let result = target.apply_changeset(&changeset, |_| true, |_, _| ConflictAction::Abort);
+    assert!(result.is_err());
+    let count = ok!(target.query_one::<(i64,), _, _>("SELECT COUNT(*) FROM items", ()));
+    assert_eq!(count.0, 0);
+
+    let inverted = ok!(Changeset::invert(&changeset));
+    ok!(source.apply_changeset(&inverted, |_| true, |_, _| ConflictAction::Abort));
+    let count = ok!(source.query_one::<(i64,), _, _>("SELECT COUNT(*) FROM users", ()));
+    assert_eq!(count.0, 0);
+}
+
+#[test]
+fn apply_changeset_foreign_key() {
+    let schema = "
+        CREATE TABLE users (id INTEGER PRIMARY KEY);
+        CREATE TABLE items (
+            id INTEGER PRIMARY KEY,
+            user_id INTEGER REFERENCES users (id) DEFERRABLE INITIALLY DEFERRED
+        );
+    ";
+    let source = ok!(Connection::open(":memory:"));
+    let target = ok!(Connection::open(":memory:"));
+    ok!(source.execute(schema));
+    ok!(target.execute(schema));
+    ok!(target.execute("PRAGMA foreign_keys = ON"));
+
+    let mut session = ok!(Session::new(&source, "main"));
+    ok!(session.attach_all());
+    ok!(source.execute("INSERT INTO items VALUES (1, 42)"));
+    let changeset = ok!(session.changeset());
+
+    let mut conflicts = Vec::new();
+    let result = target.apply_changeset(
+        &changeset,
+        |_| true,
+        |kind, change| {
+            conflicts.push((kind, change.is_some()));
+            ConflictAction::Abort
+        },
+    );
+    assert!(result.is_err());
+    assert_eq!(conflicts, [(ConflictType::ForeignKey(1), false)]);
+    let count = ok!(target.query_one::<(i64,), _, _>("SELECT COUNT(*) FROM items", ()));
+    assert_eq!(count.0, 0);
+
+    ok!(target.apply_changeset(&changeset, |_| true, |_, _| ConflictAction::Omit));
+    let count = ok!(target.query_one::<(i64,), _, _>("SELECT COUNT(*) FROM items", ()));
+    assert_eq!(count.0, 1);
+}
+
+#[test]
+fn changeset() {
+    let connection = setup();
+    ok!(connection.execute("INSERT INTO users VALUES (1, 'Alice', 42)"));
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob', 69)"));
+
+    let mut session = ok!(Session::new(&connection, "main"));
+    ok!(session.attach("users"));
+    assert!(session.is_empty());
+    ok!(connection.execute("UPDATE users SET age = 43 WHERE id = 1"));
+    ok!(connection.execute("DELETE FROM users WHERE id = 2"));
+    session.set_indirect(true);
+    ok!(connection.execute("INSERT INTO users VALUES (3, X'00', NULL)"));
+    session.set_enabled(false);
+    ok!(connection.execute("INSERT INTO users VALUES (4, 'Dave', 1)"));
+    ok!(connection.execute("INSERT INTO items VALUES (1)"));
+    assert!(!session.is_empty());
+
+    let changeset = ok!(session.changeset());
+    let mut changes = ok!(ok!(Changeset::new(&changeset)).collect::<Result<Vec<_>, _>>());
+    changes.sort_by_key(|change| change.old[0].clone().or(change.new[0].clone()).map(integer));
+    assert_eq!(
+        changes,
+        [
+            Change {
+                operation: Operation::Update,
+                table: "users".into(),
+                indirect: false,
+                old: vec![Some(Value::Integer(1)), None, Some(Value::Integer(42))],
+                new: vec![None, None, Some(Value::Integer(43))],
+            },
+            Change {
+                operation: Operation::Delete,
+                table: "users".into(),
+                indirect: false,
+                old: vec![
+                    Some(Value::Integer(2)),
+                    Some(Value::String("Bob".into())),
+                    Some(Value::Integer(69)),
+                ],
+                new: vec![None, None, None],
+            },
+            Change {
+                operation: Operation::Insert,
+                table: "users".into(),
+                indirect: true,
+                old: vec![None, None, None],
+                new: vec![
+                    Some(Value::Integer(3)),
+                    Some(Value::Binary(vec![0])),
+                    Some(Value::Null),
+                ],
+            },
+        ],
+    );
+
+    let patchset = ok!(session.patchset());
+    assert!(patchset.len() < changeset.len());
+    let change = ok!(ok!(Changeset::new(&patchset))
+        .find(|change| matches!(change, Ok(change) if change.operation == Operation::Delete))
+        .unwrap());
+    assert_eq!(change.old, [Some(Value::Integer(2)), None, None]);
+
+    let mut buffer = Vec::new();
+    ok!(session.write_changeset(&mut buffer));
+    assert_eq!(buffer, changeset);
+    let mut buffer = Vec::new();
+    ok!(session.write_patchset(&mut buffer));
+    assert_eq!(buffer, patchset);
+
+    assert!(Changeset::new(b"\x00\x01")
+        .and_then(|mut changes| changes.next().unwrap())
+        .is_err());
+}
+
+#[test]
+fn concat() {
+    let connection = setup();
+    let mut session = ok!(Session::new(&connection, "main"));
+    ok!(session.attach("users"));
+    ok!(connection.execute("INSERT INTO users VALUES (1, 'Alice', 42)"));
+    let first = ok!(session.changeset());
+    drop(session);
+
+    let mut session = ok!(Session::new(&connection, "main"));
+    ok!(session.attach("users"));
+    ok!(connection.execute("UPDATE users SET age = 43 WHERE id = 1"));
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob', 69)"));
+    let second = ok!(session.changeset());
+
+    let changeset = ok!(Changeset::concat(&first, &second));
+    let changes = ok!(ok!(Changeset::new(&changeset)).collect::<Result<Vec<_>, _>>());
+    assert_eq!(changes.len(), 2);
+    assert!(changes
+        .iter()
+        .all(|change| change.operation == Operation::Insert));
+    assert!(changes
+        .iter()
+        .any(|change| change.new[2] == Some(Value::Integer(43))));
+}
+
+fn integer(value: Value) -> i64 {
+    ok!(i64::try_from(&value))
+}
+
+fn setup() -> Connection {
+    let connection = ok!(Connection::open(":memory:"));
+    ok!(connection.execute(
+        "
+        CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, age INTEGER);
+        CREATE TABLE items (id INTEGER PRIMARY KEY);
+        ",
+    ));
+    connection
+}
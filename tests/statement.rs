@@ -1,4 +1,11 @@
-use sqlite::{Connection, State, Statement, Type, Value};
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use sqlite::{
+    Connection, ErrorKind, FromSql, State, Statement, StatementStatus, ToSql, ToSqlOutput, Type,
+    Value, ValueRef,
+};
 
 mod common;
 
@@ -65,6 +72,217 @@ fn bind_with_index() {
     assert_eq!(ok!(statement.next()), State::Done);
 }
 
+#[test]
+fn bind_with_array() {
+    let connection = setup_users(":memory:");
+    let query = "INSERT INTO users VALUES (?, ?, ?, ?, ?)";
+    let mut statement = ok!(connection.prepare(query));
+
+    ok!(statement.bind([
+        Value::Integer(2),
+        Value::String("Bob".into()),
+        Value::Float(69.42),
+        Value::Binary([0x69u8, 0x42u8].to_vec()),
+        Value::Null,
+    ]));
+    assert_eq!(ok!(statement.next()), State::Done);
+
+    let query = "SELECT * FROM users WHERE id IN (?, ?, ?)";
+    let mut statement = ok!(connection.prepare(query));
+    ok!(statement.bind([1, 2, 3]));
+    assert_eq!(ok!(statement.next()), State::Row);
+}
+
+#[test]
+fn bind_with_params() {
+    let connection = setup_users(":memory:");
+    let query = "INSERT INTO users VALUES (?, ?, :age, ?, :email)";
+    let mut statement = ok!(connection.prepare(query));
+
+    ok!(statement.bind(sqlite::params![
+        2,
+        "Bob",
+        ":age" => 69.42,
+        &[0x69u8, 0x42u8][..],
+        ":email" => None::<&str>,
+    ]));
+    assert_eq!(ok!(statement.next()), State::Done);
+
+    let query = "SELECT name FROM users WHERE id = ? AND age > ?";
+    let mut statement = ok!(connection.prepare(query));
+    ok!(statement.bind(sqlite::params![2, 69.0]));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(ok!(statement.read::<String, _>(0)), "Bob");
+
+    ok!(statement.reset());
+    ok!(statement.bind(sqlite::params![]));
+    assert!(statement.bind(sqlite::params![":missing" => 1]).is_err());
+}
+
+#[test]
+fn bind_with_tuple() {
+    let connection = setup_users(":memory:");
+    let query = "INSERT INTO users VALUES (?, ?, ?, ?, ?)";
+    let mut statement = ok!(connection.prepare(query));
+
+    ok!(statement.bind((2i64, "Bob", 69.42, &[0x69u8, 0x42u8][..], ())));
+    assert_eq!(ok!(statement.next()), State::Done);
+
+    ok!(statement.reset());
+    ok!(statement.bind((
+        Some(3i64),
+        String::from("Carol"),
+        None::<f64>,
+        vec![0x69u8],
+        Value::Null,
+    )));
+    assert_eq!(ok!(statement.next()), State::Done);
+
+    let query = "SELECT name FROM users WHERE id = ?";
+    let mut statement = ok!(connection.prepare(query));
+    ok!(statement.bind((3,)));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(ok!(statement.read::<String, _>(0)), "Carol");
+}
+
+#[test]
+fn bind_with_conversion() {
+    let connection = ok!(Connection::open(":memory:"));
+    ok!(connection.execute("CREATE TABLE data (value)"));
+    let query = "INSERT INTO data VALUES (?)";
+    let mut statement = ok!(connection.prepare(query));
+
+    macro_rules! bind(
+        ($value:expr) => ({
+            ok!(statement.reset());
+            ok!(statement.bind((1, $value)));
+            assert_eq!(ok!(statement.next()), State::Done);
+        });
+    );
+
+    bind!(-8i8);
+    bind!(-16i16);
+    bind!(-32i32);
+    bind!(8u8);
+    bind!(16u16);
+    bind!(32u32);
+    bind!(64u64);
+    bind!(-1isize);
+    bind!(1usize);
+    bind!(true);
+    bind!(0.5f32);
+    bind!('x');
+    bind!(Box::<str>::from("box"));
+    bind!(Cow::Borrowed("cow"));
+    bind!(Rc::<str>::from("rc"));
+    bind!(Arc::<str>::from("arc"));
+    bind!([0x42u8, 0x69u8]);
+
+    let query = "SELECT value FROM data";
+    let mut statement = ok!(connection.prepare(query));
+    let mut values = Vec::new();
+    while let State::Row = ok!(statement.next()) {
+        values.push(ok!(statement.read::<Value, _>(0)));
+    }
+    assert_eq!(
+        values,
+        vec![
+            Value::Integer(-8),
+            Value::Integer(-16),
+            Value::Integer(-32),
+            Value::Integer(8),
+            Value::Integer(16),
+            Value::Integer(32),
+            Value::Integer(64),
+            Value::Integer(-1),
+            Value::Integer(1),
+            Value::Integer(1),
+            Value::Float(0.5),
+            Value::String("x".into()),
+            Value::String("box".into()),
+            Value::String("cow".into()),
+            Value::String("rc".into()),
+            Value::String("arc".into()),
+            Value::Binary(vec![0x42, 0x69]),
+        ],
+    );
+
+    let query = "INSERT INTO data VALUES (?)";
+    let mut statement = ok!(connection.prepare(query));
+    match statement.bind((1, u64::MAX)) {
+        Err(error) => assert_eq!(error.kind, ErrorKind::OutOfRange),
+        _ => unreachable!(),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct UserId(i64);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Role {
+    Admin,
+    Guest,
+}
+
+impl FromSql for UserId {
+    fn from_sql(value: ValueRef<'_>) -> sqlite::Result<Self> {
+        i64::try_from(value).map(UserId)
+    }
+}
+
+impl ToSql for UserId {
+    fn to_sql(&self) -> sqlite::Result<ToSqlOutput<'_>> {
+        Ok(ValueRef::Integer(self.0).into())
+    }
+}
+
+impl FromSql for Role {
+    fn from_sql(value: ValueRef<'_>) -> sqlite::Result<Self> {
+        match <&str>::try_from(value)? {
+            "admin" => Ok(Role::Admin),
+            "guest" => Ok(Role::Guest),
+            _ => Err(sqlite::Error {
+                code: None,
+                message: Some("unknown role".into()),
+                kind: ErrorKind::Other,
+            }),
+        }
+    }
+}
+
+impl ToSql for Role {
+    fn to_sql(&self) -> sqlite::Result<ToSqlOutput<'_>> {
+        Ok(Value::from(match self {
+            Role::Admin => "admin",
+            Role::Guest => "guest",
+        })
+        .into())
+    }
+}
+
+#[test]
+fn bind_with_to_sql() {
+    let connection = ok!(Connection::open(":memory:"));
+    ok!(connection.execute("CREATE TABLE users (id INTEGER, role TEXT)"));
+    let query = "INSERT INTO users VALUES (?, ?)";
+    let mut statement = ok!(connection.prepare(query));
+    ok!(statement.bind(sqlite::params![UserId(1), Role::Admin]));
+    assert_eq!(ok!(statement.next()), State::Done);
+    ok!(statement.reset());
+    ok!(statement.bind((1, &UserId(2))));
+    ok!(statement.bind((2, Some(Role::Guest))));
+    assert_eq!(ok!(statement.next()), State::Done);
+
+    let query = "SELECT * FROM users ORDER BY id";
+    let mut statement = ok!(connection.prepare(query));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(ok!(statement.read::<i64, _>(0)), 1);
+    assert_eq!(ok!(statement.read::<String, _>(1)), "admin");
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(ok!(statement.read::<i64, _>(0)), 2);
+    assert_eq!(ok!(statement.read::<String, _>(1)), "guest");
+}
+
 #[test]
 fn bind_with_name() {
     let connection = setup_users(":memory:");
@@ -132,6 +350,78 @@ fn read_with_index() {
     assert_eq!(ok!(statement.next()), State::Done);
 }
 
+#[test]
+fn read_with_conversion() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("UPDATE users SET id = 300, email = 'a'"));
+    let query = "SELECT * FROM users";
+    let mut statement = ok!(connection.prepare(query));
+    assert_eq!(ok!(statement.next()), State::Row);
+
+    assert_eq!(ok!(statement.read::<i16, _>("id")), 300);
+    assert_eq!(ok!(statement.read::<u16, _>("id")), 300);
+    assert_eq!(ok!(statement.read::<u64, _>("id")), 300);
+    assert_eq!(ok!(statement.read::<usize, _>("id")), 300);
+    assert_eq!(ok!(statement.read::<isize, _>("id")), 300);
+    assert!(ok!(statement.read::<bool, _>("id")));
+    assert_eq!(ok!(statement.read::<f32, _>("age")), 42.69);
+    assert_eq!(ok!(statement.read::<char, _>("email")), 'a');
+    assert!(statement.read::<char, _>("name").is_err());
+    assert_eq!(&*ok!(statement.read::<Box<str>, _>("name")), "Alice");
+    assert_eq!(ok!(statement.read::<Cow<str>, _>("name")), "Alice");
+    assert_eq!(&*ok!(statement.read::<Rc<str>, _>("name")), "Alice");
+    assert_eq!(&*ok!(statement.read::<Arc<str>, _>("name")), "Alice");
+    assert_eq!(ok!(statement.read::<[u8; 2], _>("photo")), [0x42, 0x69]);
+    assert!(statement.read::<[u8; 3], _>("photo").is_err());
+    assert_eq!(ok!(statement.read::<Option<u16>, _>("id")), Some(300));
+
+    match statement.read::<u8, _>("id") {
+        Err(error) => assert_eq!(error.kind, ErrorKind::OutOfRange),
+        _ => unreachable!(),
+    }
+    match statement.read::<i8, _>("id") {
+        Err(error) => assert_eq!(error.kind, ErrorKind::OutOfRange),
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn read_with_from_sql() {
+    let connection = ok!(Connection::open(":memory:"));
+    let query = "SELECT 1, 'admin', NULL, 'owner'";
+    let mut statement = ok!(connection.prepare(query));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(ok!(statement.read::<UserId, _>(0)), UserId(1));
+    assert_eq!(ok!(statement.read::<Role, _>(1)), Role::Admin);
+    assert_eq!(ok!(statement.read::<Option<Role>, _>(2)), None);
+    assert!(statement.read::<Role, _>(3).is_err());
+    assert!(statement.read::<UserId, _>(1).is_err());
+    assert_eq!(
+        ok!(connection.query_one::<(UserId, Role), _, _>("SELECT 1, 'admin'", ())),
+        (UserId(1), Role::Admin)
+    );
+}
+
+#[test]
+fn read_ref() {
+    let connection = setup_users(":memory:");
+    let query = "SELECT * FROM users";
+    let mut statement = ok!(connection.prepare(query));
+    assert_eq!(ok!(statement.next()), State::Row);
+
+    let photo = ok!(statement.read_ref("photo"));
+    let name = ok!(statement.read_ref("name"));
+    assert_eq!(ok!(statement.read::<String, _>("photo")), "\x42\x69");
+    assert_eq!(photo, ValueRef::Blob(&[0x42, 0x69]));
+    assert_eq!(name, ValueRef::Text("Alice"));
+    assert_eq!(name.kind(), Type::String);
+    assert_eq!(ok!(statement.read_ref(0)), ValueRef::Integer(1));
+    assert_eq!(ok!(statement.read_ref(2)), ValueRef::Real(42.69));
+    assert_eq!(ok!(statement.read_ref(4)), ValueRef::Null);
+    assert_eq!(Value::from(name), Value::String("Alice".into()));
+    assert!(statement.read_ref(5).is_err());
+}
+
 #[test]
 fn read_with_index_and_option() {
     let connection = setup_users(":memory:");
@@ -232,6 +522,55 @@ fn column_type() {
     assert_eq!(ok!(statement.column_type(3)), Type::Binary);
 }
 
+#[test]
+fn for_each_row() {
+    let connection = setup_users(":memory:");
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob', NULL, NULL, NULL)"));
+    let query = "SELECT * FROM users ORDER BY id";
+    let mut statement = ok!(connection.prepare(query));
+
+    let mut rows = Vec::new();
+    ok!(statement.for_each_row(|row| {
+        assert!(row.contains("email"));
+        rows.push((
+            row.try_read::<i64, _>("id")?,
+            row.try_read::<&str, _>(1)?.to_string(),
+            row.try_read::<Option<f64>, _>("age")?,
+        ));
+        assert_eq!(row["name"], Value::String(rows.last().unwrap().1.clone()));
+        Ok(())
+    }));
+    assert_eq!(
+        rows,
+        vec![(1, "Alice".into(), Some(42.69)), (2, "Bob".into(), None)],
+    );
+
+    ok!(statement.reset());
+    let mut count = 0;
+    let result = statement.for_each_row(|row| {
+        count += 1;
+        row.try_read::<f64, _>("age").map(|_| ())
+    });
+    assert!(result.is_err());
+    assert_eq!(count, 2);
+
+    let mut statement = ok!(connection.prepare("SELECT id, name FROM users ORDER BY id"));
+    let mut values = Vec::new();
+    ok!(statement.for_each_row(|row| {
+        values.extend(row.iter().map(|(_, value)| value.clone()));
+        Ok(())
+    }));
+    assert_eq!(
+        values,
+        [
+            Value::Integer(1),
+            Value::String("Alice".into()),
+            Value::Integer(2),
+            Value::String("Bob".into()),
+        ],
+    );
+}
+
 #[test]
 fn parameter_index() {
     let connection = setup_users(":memory:");
@@ -246,6 +585,36 @@ fn parameter_index() {
     assert_eq!(ok!(statement.next()), State::Done);
 }
 
+#[test]
+fn status() {
+    let connection = setup_english(":memory:");
+    let query = "SELECT value FROM english ORDER BY value";
+    let mut statement = ok!(connection.prepare(query));
+
+    assert!(statement.is_readonly());
+    assert!(!statement.is_explain());
+    assert!(!statement.is_busy());
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert!(statement.is_busy());
+    while let State::Row = ok!(statement.next()) {}
+    assert!(!statement.is_busy());
+
+    assert_eq!(statement.status(StatementStatus::FullScanStep, false), 6);
+    assert_eq!(statement.status(StatementStatus::Sort, false), 1);
+    assert_eq!(statement.status(StatementStatus::Run, false), 1);
+    assert!(statement.status(StatementStatus::VirtualMachineStep, true) > 0);
+    assert_eq!(
+        statement.status(StatementStatus::VirtualMachineStep, false),
+        0
+    );
+
+    let statement = ok!(connection.prepare("INSERT INTO english VALUES ('type')"));
+    assert!(!statement.is_readonly());
+
+    let statement = ok!(connection.prepare("EXPLAIN QUERY PLAN SELECT * FROM english"));
+    assert!(statement.is_explain());
+}
+
 #[test]
 fn workflow_1() {
     struct Database<'l> {
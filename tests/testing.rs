@@ -0,0 +1,158 @@
+#![cfg(feature = "testing")]
+
+use std::path::Path;
+
+use sqlite::{Connection, FaultInjector, FaultVfs, OpenFlags, SyncFault, WriteFault};
+use temporary::Folder;
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+#[test]
+fn fail_sync() {
+    let (folder, injector) = setup("fail_sync");
+    let connection = open(&folder, "fail_sync");
+    ok!(connection.execute("INSERT INTO users VALUES (1, 'Alice')"));
+
+    let syncs = injector.syncs();
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob')"));
+    assert!(injector.syncs() > syncs);
+
+    injector.fail_sync(1, SyncFault::Io);
+    let error = connection
+        .execute("INSERT INTO users VALUES (3, 'Carol')")
+        .unwrap_err();
+    assert_eq!(error.code, Some(sqlite::ffi::SQLITE_IOERR as isize));
+    assert_eq!(count(&connection), 2);
+    assert_integrity(&connection);
+}
+
+#[test]
+fn fail_write_with_bulk_insert() {
+    let (folder, injector) = setup("fail_write_with_bulk_insert");
+    let connection = open(&folder, "fail_write_with_bulk_insert");
+    ok!(connection.execute("INSERT INTO users VALUES (1, 'Alice')"));
+    // Spill the changes into the database file before committing.
+    ok!(connection.execute("PRAGMA cache_size = 1"));
+
+    let name = "a".repeat(1000);
+    let mut inserter = ok!(connection.bulk_insert("users", &["name"]));
+    for _ in 0..3 {
+        ok!(inserter.insert((name.as_str(),)));
+    }
+    injector.fail_write(1, WriteFault::Full);
+    while inserter.insert((name.as_str(),)).is_ok() {}
+    assert!(inserter.inserted() >= 3);
+    let error = inserter.insert((name.as_str(),)).unwrap_err();
+    assert!(error
+        .message
+        .as_ref()
+        .unwrap()
+        .ends_with("(the transaction has been rolled back)"));
+    assert_eq!(inserter.inserted(), 0);
+    ok!(inserter.insert((name.as_str(),)));
+    assert_eq!(ok!(inserter.finish()), 1);
+    assert_eq!(count(&connection), 2);
+    assert_integrity(&connection);
+}
+
+#[test]
+fn fail_write() {
+    let (folder, injector) = setup("fail_write");
+    let connection = open(&folder, "fail_write");
+    ok!(connection.execute("INSERT INTO users VALUES (1, 'Alice')"));
+
+    for (fault, code) in [
+        (WriteFault::Full, sqlite::ffi::SQLITE_FULL),
+        (WriteFault::Io, sqlite::ffi::SQLITE_IOERR),
+    ] {
+        for nth in 1..4 {
+            injector.fail_write(nth, fault);
+            let error = connection
+                .execute("INSERT INTO users SELECT id + 100, name FROM users")
+                .unwrap_err();
+            assert_eq!(error.code, Some(code as isize));
+            assert_eq!(count(&connection), 1);
+            assert_integrity(&connection);
+        }
+    }
+
+    let writes = injector.writes();
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob')"));
+    assert!(injector.writes() > writes);
+}
+
+#[test]
+fn power_loss() {
+    let (folder, injector) = setup("power_loss");
+    let connection = open(&folder, "power_loss");
+    ok!(connection.execute("INSERT INTO users VALUES (1, 'Alice')"));
+    ok!(connection.execute("PRAGMA synchronous = OFF"));
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob')"));
+    assert_eq!(count(&connection), 2);
+
+    ok!(injector.power_loss());
+    assert!(connection
+        .execute("INSERT INTO users VALUES (3, 'Carol')")
+        .is_err());
+    drop(connection);
+
+    let connection = open(&folder, "power_loss");
+    assert_eq!(count(&connection), 1);
+    assert_integrity(&connection);
+}
+
+#[test]
+fn power_loss_with_torn_write() {
+    let (folder, injector) = setup("power_loss_with_torn_write");
+    let connection = open(&folder, "power_loss_with_torn_write");
+    ok!(connection.execute("INSERT INTO users VALUES (1, 'Alice')"));
+
+    injector.fail_write(1, WriteFault::Torn(100));
+    ok!(connection.execute("BEGIN"));
+    ok!(connection.execute("INSERT INTO users VALUES (2, 'Bob')"));
+    ok!(connection.execute("UPDATE users SET name = 'Alicia' WHERE id = 1"));
+    // Spill the changes into the database file without committing.
+    ok!(connection.execute("PRAGMA cache_size = 1"));
+    ok!(connection.execute(
+        "
+        WITH RECURSIVE series(value) AS (SELECT 1 UNION ALL SELECT value + 1 FROM series LIMIT 100)
+        INSERT INTO users SELECT value + 10, hex(randomblob(1000)) FROM series
+        ",
+    ));
+    ok!(injector.power_loss());
+    drop(connection);
+
+    let connection = open(&folder, "power_loss_with_torn_write");
+    let query = "SELECT name FROM users";
+    let names = ok!(connection.query_all::<(String,), _, _>(query, ()));
+    assert_eq!(names, [("Alice".into(),)]);
+    assert_integrity(&connection);
+}
+
+fn assert_integrity(connection: &Connection) {
+    let query = "PRAGMA integrity_check";
+    let (result,) = ok!(connection.query_one::<(String,), _, _>(query, ()));
+    assert_eq!(result, "ok");
+}
+
+fn count(connection: &Connection) -> i64 {
+    let query = "SELECT count(*) FROM users";
+    ok!(connection.query_one::<(i64,), _, _>(query, ())).0
+}
+
+fn open(folder: &Folder, vfs: &str) -> Connection {
+    let path = folder.path().join("database.sqlite3");
+    let flags = OpenFlags::new().with_create().with_read_write();
+    ok!(Connection::open_with_flags_and_vfs(path, flags, vfs))
+}
+
+fn setup(vfs: &str) -> (Folder, FaultInjector) {
+    let folder = ok!(Folder::new("sqlite"));
+    let fault = ok!(FaultVfs::new());
+    let injector = fault.injector();
+    ok!(sqlite::register_vfs(vfs, fault, false));
+    let connection = open(&folder, vfs);
+    ok!(connection.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)"));
+    assert!(Path::new(&folder.path().join("database.sqlite3")).exists());
+    (folder, injector)
+}
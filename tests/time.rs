@@ -0,0 +1,78 @@
+#![cfg(feature = "time")]
+
+use sqlite::{Connection, State, Value};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+fn date_time() -> PrimitiveDateTime {
+    PrimitiveDateTime::new(
+        ok!(Date::from_calendar_date(2024, Month::February, 29)),
+        ok!(Time::from_hms_milli(13, 14, 15, 500)),
+    )
+}
+
+#[test]
+fn bind() {
+    let connection = ok!(Connection::open(":memory:"));
+    let query = "SELECT ?, ?, ?, ?, datetime(?1)";
+    let mut statement = ok!(connection.prepare(query));
+    let offset = ok!(UtcOffset::from_hms(-5, -30, 0));
+    ok!(statement.bind((1, date_time().assume_offset(offset))));
+    ok!(statement.bind((2, date_time())));
+    ok!(statement.bind((3, date_time().date())));
+    ok!(statement.bind((4, date_time().time())));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(
+        ok!(statement.read::<String, _>(0)),
+        "2024-02-29 13:14:15.5-05:30"
+    );
+    assert_eq!(ok!(statement.read::<String, _>(1)), "2024-02-29 13:14:15.5");
+    assert_eq!(ok!(statement.read::<String, _>(2)), "2024-02-29");
+    assert_eq!(ok!(statement.read::<String, _>(3)), "13:14:15.5");
+    assert_eq!(ok!(statement.read::<String, _>(4)), "2024-02-29 18:44:15");
+}
+
+#[test]
+fn read() {
+    let connection = ok!(Connection::open(":memory:"));
+    let query = "
+        SELECT
+            '2024-02-29 13:14:15.500',
+            '2024-02-29 07:44:15.5-05:30',
+            julianday('2024-02-29 13:14:15.500'),
+            NULL
+    ";
+    let mut statement = ok!(connection.prepare(query));
+    assert_eq!(ok!(statement.next()), State::Row);
+    for index in 0..3 {
+        assert_eq!(
+            ok!(statement.read::<OffsetDateTime, _>(index)),
+            date_time().assume_utc()
+        );
+        assert_eq!(
+            ok!(statement.read::<PrimitiveDateTime, _>(index)),
+            date_time()
+        );
+        assert_eq!(ok!(statement.read::<Date, _>(index)), date_time().date());
+        assert_eq!(ok!(statement.read::<Time, _>(index)), date_time().time());
+    }
+    let value = ok!(statement.read::<OffsetDateTime, _>(1));
+    assert_eq!(value.offset().whole_seconds(), -(5 * 3600 + 30 * 60));
+    assert_eq!(ok!(statement.read::<Option<Date>, _>(3)), None);
+}
+
+#[test]
+fn try_from() {
+    assert_eq!(
+        ok!(OffsetDateTime::try_from(&Value::Integer(0))),
+        OffsetDateTime::UNIX_EPOCH
+    );
+    assert_eq!(
+        ok!(Time::try_from(&Value::from("23:59:59.999999999"))),
+        ok!(Time::from_hms_nano(23, 59, 59, 999_999_999))
+    );
+    assert!(Date::try_from(&Value::from("2024-13-01")).is_err());
+    assert!(Time::try_from(&Value::from("12:00:00.")).is_err());
+    assert_eq!(ok!(Option::<Date>::try_from(&Value::Null)), None);
+}
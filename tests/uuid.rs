@@ -0,0 +1,52 @@
+#![cfg(feature = "uuid")]
+
+use sqlite::{Connection, State, Type, Value};
+use uuid::Uuid;
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+const UUID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+#[test]
+fn bind() {
+    let connection = ok!(Connection::open(":memory:"));
+    let uuid = ok!(Uuid::parse_str(UUID));
+    let mut statement = ok!(connection.prepare("SELECT ?, ?"));
+    ok!(statement.bind((1, uuid)));
+    ok!(statement.bind((2, uuid.hyphenated())));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(ok!(statement.column_type(0)), Type::Binary);
+    assert_eq!(ok!(statement.read::<Vec<u8>, _>(0)), uuid.as_bytes());
+    assert_eq!(ok!(statement.read::<String, _>(1)), UUID);
+}
+
+#[test]
+fn read() {
+    let connection = ok!(Connection::open(":memory:"));
+    let uuid = ok!(Uuid::parse_str(UUID));
+    let query = "
+        SELECT
+            X'67e5504410b1426f9247bb680e5fe0c8',
+            '67e55044-10b1-426f-9247-bb680e5fe0c8',
+            '67E5504410B1426F9247BB680E5FE0C8',
+            X'67e5',
+            NULL
+    ";
+    let mut statement = ok!(connection.prepare(query));
+    assert_eq!(ok!(statement.next()), State::Row);
+    assert_eq!(ok!(statement.read::<Uuid, _>(0)), uuid);
+    assert_eq!(ok!(statement.read::<Uuid, _>(1)), uuid);
+    assert_eq!(ok!(statement.read::<Uuid, _>(2)), uuid);
+    assert!(statement.read::<Uuid, _>(3).is_err());
+    assert_eq!(ok!(statement.read::<Option<Uuid>, _>(4)), None);
+}
+
+#[test]
+fn value() {
+    let uuid = ok!(Uuid::parse_str(UUID));
+    assert_eq!(Value::from(uuid), Value::Binary(uuid.as_bytes().to_vec()));
+    assert_eq!(Value::from(uuid.hyphenated()), Value::String(UUID.into()));
+    assert_eq!(ok!(Uuid::try_from(&Value::from(uuid))), uuid);
+    assert_eq!(ok!(Uuid::try_from(&Value::from(UUID))), uuid);
+    assert!(Uuid::try_from(&Value::Integer(42)).is_err());
+}
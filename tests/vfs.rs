@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use sqlite::{AccessCheck, Connection, FileKind, FileOptions, LockLevel, OpenFlags, Vfs, VfsFile};
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+type Files = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+#[derive(Default)]
+struct Memory {
+    files: Files,
+    kinds: Arc<Mutex<Vec<FileKind>>>,
+    counter: AtomicUsize,
+    faults: Arc<Faults>,
+    long_pathname: bool,
+}
+
+#[derive(Default)]
+struct Faults {
+    busy: AtomicBool,
+    full: AtomicBool,
+    sync: AtomicBool,
+}
+
+struct MemoryFile {
+    files: Files,
+    path: String,
+    delete: bool,
+    faults: Arc<Faults>,
+}
+
+impl Vfs for Memory {
+    type File = MemoryFile;
+
+    fn open(&self, path: Option<&str>, options: FileOptions) -> sqlite::Result<MemoryFile> {
+        let path = match path {
+            Some(path) => path.to_string(),
+            _ => format!("temporary-{}", self.counter.fetch_add(1, Ordering::Relaxed)),
+        };
+        let mut files = self.files.lock().unwrap();
+        if !files.contains_key(&path) && !options.is_create() {
+            return Err(error(sqlite::ffi::SQLITE_CANTOPEN));
+        }
+        files.entry(path.clone()).or_default();
+        self.kinds.lock().unwrap().push(options.kind());
+        Ok(MemoryFile {
+            files: self.files.clone(),
+            path,
+            delete: options.is_delete_on_close(),
+            faults: self.faults.clone(),
+        })
+    }
+
+    fn delete(&self, path: &str, _: bool) -> sqlite::Result<()> {
+        self.files.lock().unwrap().remove(path);
+        Ok(())
+    }
+
+    fn access(&self, path: &str, _: AccessCheck) -> sqlite::Result<bool> {
+        Ok(self.files.lock().unwrap().contains_key(path))
+    }
+
+    fn full_pathname(&self, path: &str) -> sqlite::Result<String> {
+        if self.long_pathname {
+            return Ok(path.repeat(10000));
+        }
+        Ok(path.into())
+    }
+}
+
+impl VfsFile for MemoryFile {
+    fn read(&mut self, buffer: &mut [u8], offset: u64) -> sqlite::Result<usize> {
+        let files = self.files.lock().unwrap();
+        let data = files[&self.path].get(offset as usize..).unwrap_or_default();
+        let count = data.len().min(buffer.len());
+        buffer[..count].copy_from_slice(&data[..count]);
+        Ok(count)
+    }
+
+    fn write(&mut self, data: &[u8], offset: u64) -> sqlite::Result<()> {
+        if self.faults.full.load(Ordering::Relaxed) {
+            return Err(error(sqlite::ffi::SQLITE_FULL));
+        }
+        let mut files = self.files.lock().unwrap();
+        let file = files.get_mut(&self.path).unwrap();
+        let end = offset as usize + data.len();
+        if file.len() < end {
+            file.resize(end, 0);
+        }
+        file[offset as usize..end].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn truncate(&mut self, size: u64) -> sqlite::Result<()> {
+        let mut files = self.files.lock().unwrap();
+        files.get_mut(&self.path).unwrap().resize(size as usize, 0);
+        Ok(())
+    }
+
+    fn sync(&mut self, _: bool) -> sqlite::Result<()> {
+        if self.faults.sync.load(Ordering::Relaxed) {
+            return Err(sqlite::Error {
+                code: None,
+                message: Some("failed to sync".into()),
+                kind: sqlite::ErrorKind::Other,
+            });
+        }
+        Ok(())
+    }
+
+    fn file_size(&self) -> sqlite::Result<u64> {
+        Ok(self.files.lock().unwrap()[&self.path].len() as u64)
+    }
+
+    fn lock(&mut self, level: LockLevel) -> sqlite::Result<()> {
+        if level >= LockLevel::Reserved && self.faults.busy.load(Ordering::Relaxed) {
+            return Err(error(sqlite::ffi::SQLITE_BUSY));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for MemoryFile {
+    fn drop(&mut self) {
+        if self.delete {
+            self.files.lock().unwrap().remove(&self.path);
+        }
+    }
+}
+
+#[test]
+fn register_vfs() {
+    let vfs = Memory::default();
+    let files = vfs.files.clone();
+    let kinds = vfs.kinds.clone();
+    ok!(sqlite::register_vfs("register_vfs", vfs, false));
+    assert!(sqlite::register_vfs("register_vfs", Memory::default(), false).is_err());
+
+    let flags = OpenFlags::new().with_create().with_read_write();
+    let connection = ok!(Connection::open_with_flags_and_vfs(
+        "database",
+        flags,
+        "register_vfs"
+    ));
+    ok!(connection.execute("CREATE TABLE users (id INTEGER, name TEXT)"));
+    ok!(connection.execute("INSERT INTO users VALUES (1, 'Alice'), (2, 'Bob')"));
+    assert!(files.lock().unwrap()["database"].len() >= 4096);
+    assert!(kinds.lock().unwrap().contains(&FileKind::MainDb));
+    assert!(kinds.lock().unwrap().contains(&FileKind::MainJournal));
+
+    let flags = OpenFlags::new().with_read_only();
+    let other = ok!(Connection::open_with_flags_and_vfs(
+        "database",
+        flags,
+        "register_vfs"
+    ));
+    let query = "SELECT name FROM users ORDER BY id";
+    let names = ok!(other.query_all::<(String,), _, _>(query, ()));
+    assert_eq!(names, [("Alice".into(),), ("Bob".into(),)]);
+
+    assert!(Connection::open_with_flags_and_vfs("missing", flags, "register_vfs").is_err());
+    assert!(Connection::open_with_flags_and_vfs("database", flags, "unknown").is_err());
+}
+
+#[test]
+fn register_vfs_concurrently() {
+    let threads = (0..8)
+        .map(|_| {
+            std::thread::spawn(|| {
+                sqlite::register_vfs("register_vfs_concurrently", Memory::default(), false)
+            })
+        })
+        .collect::<Vec<_>>();
+    let registered = threads
+        .into_iter()
+        .map(|thread| ok!(thread.join()))
+        .filter(Result::is_ok)
+        .count();
+    assert_eq!(registered, 1);
+}
+
+#[test]
+fn register_vfs_default() {
+    let vfs = Memory::default();
+    let files = vfs.files.clone();
+    ok!(sqlite::register_vfs("register_vfs_default", vfs, true));
+
+    let connection = ok!(sqlite::open("register_vfs_default.sqlite3"));
+    ok!(connection.execute("CREATE TABLE users (id INTEGER, name TEXT)"));
+    assert!(files
+        .lock()
+        .unwrap()
+        .contains_key("register_vfs_default.sqlite3"));
+}
+
+#[test]
+fn full_pathname() {
+    let vfs = Memory {
+        long_pathname: true,
+        ..Default::default()
+    };
+    ok!(sqlite::register_vfs("full_pathname", vfs, false));
+
+    let flags = OpenFlags::new().with_create().with_read_write();
+    let result = Connection::open_with_flags_and_vfs("database", flags, "full_pathname");
+    assert_eq!(
+        result.err().unwrap().code,
+        Some(sqlite::ffi::SQLITE_CANTOPEN as isize)
+    );
+}
+
+#[test]
+fn lock() {
+    let vfs = Memory::default();
+    let faults = vfs.faults.clone();
+    ok!(sqlite::register_vfs("lock", vfs, false));
+
+    let flags = OpenFlags::new().with_create().with_read_write();
+    let connection = ok!(Connection::open_with_flags_and_vfs(
+        "database", flags, "lock"
+    ));
+    ok!(connection.execute("CREATE TABLE users (id INTEGER, name TEXT)"));
+
+    faults.busy.store(true, Ordering::Relaxed);
+    let result = connection.execute("INSERT INTO users VALUES (1, 'Alice')");
+    assert_eq!(
+        result.err().unwrap().code,
+        Some(sqlite::ffi::SQLITE_BUSY as isize)
+    );
+    let query = "SELECT count(*) FROM users";
+    assert_eq!(ok!(connection.query_one::<(i64,), _, _>(query, ())).0, 0);
+
+    faults.busy.store(false, Ordering::Relaxed);
+    ok!(connection.execute("INSERT INTO users VALUES (1, 'Alice')"));
+}
+
+#[test]
+fn write_and_sync() {
+    let vfs = Memory::default();
+    let faults = vfs.faults.clone();
+    ok!(sqlite::register_vfs("write_and_sync", vfs, false));
+
+    let flags = OpenFlags::new().with_create().with_read_write();
+    let connection = ok!(Connection::open_with_flags_and_vfs(
+        "database",
+        flags,
+        "write_and_sync"
+    ));
+    ok!(connection.execute("CREATE TABLE users (id INTEGER, name TEXT)"));
+
+    faults.full.store(true, Ordering::Relaxed);
+    let result = connection.execute("INSERT INTO users VALUES (1, 'Alice')");
+    assert_eq!(
+        result.err().unwrap().code,
+        Some(sqlite::ffi::SQLITE_FULL as isize)
+    );
+    faults.full.store(false, Ordering::Relaxed);
+
+    faults.sync.store(true, Ordering::Relaxed);
+    let result = connection.execute("INSERT INTO users VALUES (1, 'Alice')");
+    assert_eq!(
+        result.err().unwrap().code,
+        Some(sqlite::ffi::SQLITE_IOERR as isize)
+    );
+    faults.sync.store(false, Ordering::Relaxed);
+
+    ok!(connection.execute("INSERT INTO users VALUES (1, 'Alice')"));
+    let query = "SELECT count(*) FROM users";
+    assert_eq!(ok!(connection.query_one::<(i64,), _, _>(query, ())).0, 1);
+}
+
+fn error(code: std::ffi::c_int) -> sqlite::Error {
+    sqlite::Error {
+        code: Some(code as isize),
+        message: None,
+        kind: sqlite::ErrorKind::Other,
+    }
+}
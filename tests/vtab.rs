@@ -0,0 +1,263 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use sqlite::{Connection, ConstraintOperator, IndexInfo, Module, Update, VTab, VTabCursor, Value};
+
+macro_rules! ok(($result:expr) => ($result.unwrap()));
+
+type Rows = Arc<Mutex<BTreeMap<i64, (String, Value)>>>;
+
+struct Table {
+    rows: Rows,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+struct Cursor {
+    rows: Vec<(i64, (String, Value))>,
+    position: usize,
+}
+
+impl VTab for Table {
+    type Aux = (Rows, Arc<Mutex<Vec<String>>>);
+    type Cursor = Cursor;
+
+    fn connect(aux: &Self::Aux, arguments: &[&str]) -> sqlite::Result<(String, Self)> {
+        aux.1.lock().unwrap().push(arguments.join(" "));
+        if arguments.get(3) == Some(&"fail") {
+            return Err(sqlite::Error {
+                code: None,
+                message: Some("failed to connect".into()),
+                kind: Default::default(),
+            });
+        }
+        let table = Table {
+            rows: aux.0.clone(),
+            log: aux.1.clone(),
+        };
+        Ok(("CREATE TABLE x (name TEXT, value)".into(), table))
+    }
+
+    fn best_index(&self, info: &mut IndexInfo<'_>) -> sqlite::Result<()> {
+        let constraint = info.constraints().position(|constraint| {
+            constraint.usable
+                && constraint.column.is_none()
+                && constraint.operator == ConstraintOperator::Eq
+        });
+        if let Some(constraint) = constraint {
+            info.set_argument(constraint, 0, true);
+            info.set_index_number(1);
+            info.set_index_string("rowid")?;
+            info.set_estimated_cost(1.0);
+            info.set_estimated_rows(1);
+            info.set_unique(true);
+        } else {
+            info.set_estimated_cost(1000.0);
+        }
+        let order_by = info.order_by().collect::<Vec<_>>();
+        if order_by.len() == 1 && order_by[0].column.is_none() && !order_by[0].descending {
+            info.set_order_by_consumed(true);
+        }
+        Ok(())
+    }
+
+    fn open(&self) -> sqlite::Result<Cursor> {
+        let rows = self.rows.lock().unwrap();
+        Ok(Cursor {
+            rows: rows
+                .iter()
+                .map(|(rowid, row)| (*rowid, row.clone()))
+                .collect(),
+            position: 0,
+        })
+    }
+
+    fn update(&mut self, update: Update<'_>) -> sqlite::Result<i64> {
+        self.log.lock().unwrap().push(format!("{update:?}"));
+        let mut rows = self.rows.lock().unwrap();
+        let (rowid, values) = match update {
+            Update::Delete(rowid) => {
+                rows.remove(&rowid);
+                return Ok(rowid);
+            }
+            Update::Insert { rowid, values } => (
+                rowid.unwrap_or_else(|| rows.keys().last().map_or(1, |rowid| rowid + 1)),
+                values,
+            ),
+            Update::Update {
+                old_rowid,
+                rowid,
+                values,
+            } => {
+                rows.remove(&old_rowid);
+                (rowid, values)
+            }
+        };
+        let name = match &values[0] {
+            Value::String(name) => name.clone(),
+            _ => {
+                return Err(sqlite::Error {
+                    code: Some(19),
+                    message: Some("expected a name".into()),
+                    kind: Default::default(),
+                })
+            }
+        };
+        rows.insert(rowid, (name, values[1].clone()));
+        Ok(rowid)
+    }
+}
+
+impl VTabCursor for Cursor {
+    fn filter(
+        &mut self,
+        index_number: i32,
+        index_string: Option<&str>,
+        arguments: &[Value],
+    ) -> sqlite::Result<()> {
+        self.position = 0;
+        if index_number == 1 {
+            assert_eq!(index_string, Some("rowid"));
+            let rowid = i64::try_from(&arguments[0])?;
+            self.rows.retain(|row| row.0 == rowid);
+        }
+        Ok(())
+    }
+
+    fn next(&mut self) -> sqlite::Result<()> {
+        self.position += 1;
+        Ok(())
+    }
+
+    fn eof(&self) -> bool {
+        self.position >= self.rows.len()
+    }
+
+    fn column(&self, index: usize) -> sqlite::Result<Value> {
+        let row = &self.rows[self.position].1;
+        match index {
+            0 => Ok(Value::String(row.0.clone())),
+            1 => Ok(row.1.clone()),
+            _ => Err(sqlite::Error {
+                code: None,
+                message: Some("failed to read a column".into()),
+                kind: Default::default(),
+            }),
+        }
+    }
+
+    fn rowid(&self) -> sqlite::Result<i64> {
+        Ok(self.rows[self.position].0)
+    }
+}
+
+#[test]
+fn create_module() {
+    let connection = ok!(Connection::open(":memory:"));
+    let rows = Rows::default();
+    let log = Arc::new(Mutex::new(Vec::new()));
+    ok!(connection.create_module("memory", Module::<Table>::new((rows.clone(), log.clone()))));
+    ok!(connection.execute("CREATE VIRTUAL TABLE users USING memory(a, b)"));
+    assert_eq!(ok!(log.lock()).as_slice(), ["memory main users a b"]);
+    assert!(connection.execute("SELECT * FROM memory").is_err());
+
+    let error = connection
+        .execute("CREATE VIRTUAL TABLE broken USING memory(fail)")
+        .unwrap_err();
+    assert!(ok!(error.message).contains("failed to connect"));
+
+    ok!(connection.execute("INSERT INTO users VALUES ('Alice', 42)"));
+    ok!(connection.execute("INSERT INTO users (rowid, name, value) VALUES (10, 'Bob', X'69')"));
+    ok!(connection.execute("INSERT INTO users VALUES ('Carol', NULL)"));
+    assert!(connection
+        .execute("INSERT INTO users VALUES (NULL, 1)")
+        .is_err());
+    ok!(connection.execute("UPDATE users SET value = 'x' WHERE rowid = 10"));
+    ok!(connection.execute("DELETE FROM users WHERE name = 'Alice'"));
+
+    let query = "SELECT rowid, name, value FROM users ORDER BY rowid";
+    let rows = ok!(connection.query_all::<(i64, String, Value), _, _>(query, ()));
+    assert_eq!(
+        rows,
+        [
+            (10, "Bob".into(), Value::String("x".into())),
+            (11, "Carol".into(), Value::Null),
+        ],
+    );
+    let query = "SELECT name FROM users WHERE rowid = ?";
+    assert_eq!(
+        ok!(connection.query_one::<(String,), _, _>(query, (11,))).0,
+        "Carol"
+    );
+
+    let plan = ok!(connection.query_plan("SELECT name FROM users WHERE rowid = 11"));
+    assert!(format!("{plan:?}").contains("VIRTUAL TABLE INDEX 1:rowid"));
+
+    let log = ok!(log.lock());
+    let log = log
+        .iter()
+        .filter(|entry| !entry.starts_with("memory"))
+        .collect::<Vec<_>>();
+    assert!(log[0].starts_with("Insert { rowid: None"));
+    assert!(log[1].starts_with("Insert { rowid: Some(10)"));
+    assert!(log
+        .iter()
+        .any(|entry| entry.starts_with("Update { old_rowid: 10, rowid: 10")));
+    assert_eq!(log.last().unwrap().as_str(), "Delete(1)");
+}
+
+#[test]
+fn eponymous_only() {
+    let connection = ok!(Connection::open(":memory:"));
+    let rows = Rows::default();
+    ok!(rows.lock()).insert(1, ("Alice".into(), Value::Integer(42)));
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let module = Module::<Table>::eponymous_only((rows, log.clone()));
+    ok!(connection.create_module("memory", module));
+    let query = "SELECT name, value FROM memory";
+    let row = ok!(connection.query_one::<(String, i64), _, _>(query, ()));
+    assert_eq!(row, ("Alice".into(), 42));
+    assert!(connection
+        .execute("CREATE VIRTUAL TABLE users USING memory")
+        .is_err());
+}
+
+#[test]
+fn create_table_function() {
+    let connection = ok!(sqlite::open(":memory:"));
+    let columns = ["value INTEGER", "start HIDDEN", "stop HIDDEN"];
+    ok!(
+        connection.create_table_function("series", &columns, |arguments: &[Value]| {
+            let start = i64::try_from(&arguments[0]).unwrap_or(1);
+            let stop = i64::try_from(&arguments[1]).unwrap_or(start);
+            (start..=stop).map(|value| vec![Value::Integer(value)])
+        })
+    );
+
+    let query = "SELECT value, start, stop FROM series(2, 4)";
+    let rows = ok!(connection.query_all::<(i64, i64, i64), _, _>(query, ()));
+    assert_eq!(rows, [(2, 2, 4), (3, 2, 4), (4, 2, 4)]);
+
+    let query = "SELECT value FROM series WHERE start = 3";
+    let rows = ok!(connection.query_all::<(i64,), _, _>(query, ()));
+    assert_eq!(rows, [(3,)]);
+
+    let query = "SELECT count(*) FROM series(1, 3) AS a JOIN series(a.value, 3)";
+    let (count,) = ok!(connection.query_one::<(i64,), _, _>(query, ()));
+    assert_eq!(count, 6);
+
+    assert!(connection
+        .create_table_function("bad", &[], |_: &[Value]| Vec::new())
+        .is_err());
+
+    let columns = (0..32)
+        .map(|index| format!("p{index} HIDDEN"))
+        .chain(["value".into()])
+        .collect::<Vec<_>>();
+    let columns = columns.iter().map(String::as_str).collect::<Vec<_>>();
+    assert!(connection
+        .create_table_function("bad", &columns, |_: &[Value]| Vec::new())
+        .is_err());
+    ok!(connection.create_table_function("good", &columns[1..], |_: &[Value]| Vec::new()));
+    let query = "SELECT count(*) FROM good WHERE p31 = 1";
+    assert_eq!(ok!(connection.query_one::<(i64,), _, _>(query, ())).0, 0);
+}